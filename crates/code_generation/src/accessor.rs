@@ -290,6 +290,8 @@ mod tests {
             instances: vec!["InstanceA".to_string(), "InstanceB".to_string()],
             setup_nodes: vec![],
             cycle_nodes: vec![],
+            enable_cycle_budget: false,
+            recording_encoder: syn::parse_quote!(crate::cyclers::BincodeEncoder),
         };
 
         for (path, reference_type, expected_token_stream) in cases {
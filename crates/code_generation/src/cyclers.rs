@@ -1,4 +1,4 @@
-use std::{collections::HashSet, iter::once};
+use std::iter::once;
 
 use convert_case::{Case, Casing};
 use itertools::Itertools;
@@ -8,6 +8,12 @@ use source_analyzer::{
     contexts::Field,
     cyclers::{Cycler, CyclerKind, Cyclers},
     node::Node,
+    path::Path,
+    structs::{
+        unwrap_option_type, CYCLE_BUDGET_OVERRUNS_PATH, CYCLE_BUDGET_PATH,
+        MISSING_REQUIRED_INPUTS_PATH, RECORDING_DECISION_PATH, RECORDING_SERIALIZATION_TIME_PATH,
+        RECORDING_TRIGGER_PATH,
+    },
 };
 
 use crate::accessor::{path_to_accessor_token_stream, ReferenceKind};
@@ -17,6 +23,8 @@ pub fn generate_cyclers(cyclers: &Cyclers) -> TokenStream {
         let instance_name = format_ident!("{}", instance);
         quote! {
             #instance_name {
+                kind: RecordingFrameKind,
+                timestamp: std::time::SystemTime,
                 data: std::vec::Vec<u8>,
             },
         }
@@ -28,10 +36,112 @@ pub fn generate_cyclers(cyclers: &Cyclers) -> TokenStream {
         .collect();
 
     quote! {
+        /// Frame sent to the recording thread, which writes `kind` followed by `data` into the
+        /// recording file of the instance
         pub enum RecordingFrame {
             #(#recording_frame_variants)*
         }
 
+        /// Marker written in front of every frame of a recording file, distinguishing headers from
+        /// the frames of cycles
+        #[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+        pub enum RecordingFrameKind {
+            /// Data is a `RecordingHeader`
+            Header,
+            /// Data are the values listed in `recorded_fields()` of the cycler
+            Cycle,
+        }
+
+        /// First frame of every recording, written each time the recording of a cycler starts
+        #[derive(serde::Serialize)]
+        pub struct RecordingHeader<'header> {
+            pub body_id: &'header str,
+            pub head_id: &'header str,
+            pub parameters: &'header crate::structs::Parameters,
+        }
+
+        /// Value serialized into each recording frame of a cycler, listed in serialization order
+        #[derive(Clone, Copy, Debug, serde::Serialize)]
+        pub struct RecordedField {
+            pub path: &'static str,
+            pub data_type: &'static str,
+            /// Node states are written length-prefixed and skipped when decoding, since some of
+            /// them cannot be deserialized (e.g. fields skipped with `deserialize_not_implemented`)
+            pub is_node_state: bool,
+        }
+
+        /// Node executed by a cycler, listed in execution order
+        #[derive(Clone, Copy, Debug, serde::Serialize)]
+        pub struct NodeDescription {
+            pub name: &'static str,
+            pub module: &'static str,
+            pub is_setup_node: bool,
+        }
+
+        /// Serialization format of the values written into recording frames
+        pub trait FrameEncoder {
+            /// Extension of the recording and index files written with this encoder
+            const FILE_EXTENSION: &'static str;
+
+            fn serialize_value<Writer, Value>(writer: Writer, value: &Value) -> color_eyre::Result<()>
+            where
+                Writer: std::io::Write,
+                Value: serde::Serialize + ?Sized;
+
+            fn deserialize_value<Reader, Value>(reader: Reader) -> color_eyre::Result<Value>
+            where
+                Reader: std::io::Read,
+                Value: serde::de::DeserializeOwned;
+        }
+
+        pub struct BincodeEncoder;
+
+        impl FrameEncoder for BincodeEncoder {
+            const FILE_EXTENSION: &'static str = "bincode";
+
+            fn serialize_value<Writer, Value>(writer: Writer, value: &Value) -> color_eyre::Result<()>
+            where
+                Writer: std::io::Write,
+                Value: serde::Serialize + ?Sized,
+            {
+                Ok(bincode::serialize_into(writer, value)?)
+            }
+
+            fn deserialize_value<Reader, Value>(reader: Reader) -> color_eyre::Result<Value>
+            where
+                Reader: std::io::Read,
+                Value: serde::de::DeserializeOwned,
+            {
+                Ok(bincode::deserialize_from(reader)?)
+            }
+        }
+
+        /// Decodes the next value of a recording frame into JSON, values without a JSON
+        /// representation (e.g. maps with non-string keys) are replaced by the conversion error
+        pub fn decode_recorded_value<Encoder, Value>(
+            data: &mut &[u8],
+        ) -> color_eyre::Result<serde_json::Value>
+        where
+            Encoder: FrameEncoder,
+            Value: serde::Serialize + serde::de::DeserializeOwned,
+        {
+            let value: Value = Encoder::deserialize_value(data)?;
+            Ok(serde_json::to_value(value).unwrap_or_else(|error| {
+                serde_json::Value::String(format!("not representable as JSON: {error}"))
+            }))
+        }
+
+        /// Skips the next (length-prefixed) node state of a recording frame and returns a
+        /// placeholder for it
+        pub fn skip_node_state(data: &mut &[u8]) -> color_eyre::Result<serde_json::Value> {
+            let node_state = framework::read_length_prefixed(data)
+                .ok_or_else(|| color_eyre::eyre::eyre!("node state is truncated"))?;
+            Ok(serde_json::Value::String(format!(
+                "node state of {} bytes, not decoded",
+                node_state.len()
+            )))
+        }
+
         #(#cyclers)*
     }
 }
@@ -42,18 +152,95 @@ fn generate_module(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     let database_struct = generate_database_struct();
     let cycler_struct = generate_struct(cycler, cyclers);
     let cycler_implementation = generate_implementation(cycler, cyclers);
+    let main_output_default_assertions = generate_main_output_default_assertions(cycler);
+    let cross_input_serialization_assertions =
+        generate_cross_input_serialization_assertions(cycler);
+    let recorded_fields = generate_recorded_fields(cycler);
+    let recording_frame_decoder = generate_recording_frame_decoder(cycler);
+    let node_descriptions = generate_node_descriptions(cycler);
+    let recording_encoder = &cycler.recording_encoder;
 
     quote! {
         #[allow(dead_code, unused_mut, unused_variables, clippy::too_many_arguments, clippy::needless_question_mark)]
         pub(crate) mod #module_name {
             use color_eyre::eyre::WrapErr;
             use crate::structs::#module_name::{MainOutputs, AdditionalOutputs};
+            use crate::cyclers::FrameEncoder;
+
+            pub(crate) type RecordingEncoder = #recording_encoder;
 
             #cycler_instance
             #database_struct
             #cycler_struct
             #cycler_implementation
+            #main_output_default_assertions
+            #cross_input_serialization_assertions
+            #recorded_fields
+            #recording_frame_decoder
+            #node_descriptions
+        }
+    }
+}
+
+fn generate_main_output_default_assertions(cycler: &Cycler) -> TokenStream {
+    // Main outputs are reset via `Default::default()` if required inputs are missing or the cycle
+    // guard of the node is false, name the offending output in the trait bound to get a readable
+    // error instead of one deep inside the generated cycle
+    let assertions = cycler.iter_nodes().flat_map(|node| {
+        node.contexts
+            .main_outputs
+            .iter()
+            .filter_map(move |field| match field {
+                Field::MainOutput { data_type, name } => {
+                    let assertion_name = format_ident!(
+                        "main_output_{}_of_{}_must_implement_default",
+                        name,
+                        node.name.to_case(Case::Snake)
+                    );
+                    Some(quote! {
+                        const _: fn() = || {
+                            fn #assertion_name<T: Default>() {}
+                            #assertion_name::<#data_type>();
+                        };
+                    })
+                }
+                _ => None,
+            })
+    });
+    quote! {
+        #(#assertions)*
+    }
+}
+
+fn generate_cross_input_serialization_assertions(cycler: &Cycler) -> TokenStream {
+    // Cross inputs are serialized into recording frames, name the offending input in the trait
+    // bound to get a readable error instead of one deep inside the generated recording code
+    let assertions = get_cross_inputs(cycler).into_iter().map(|field| {
+        let (name, data_type) = match &field {
+            Field::CyclerState {
+                data_type, name, ..
+            }
+            | Field::Input {
+                data_type, name, ..
+            }
+            | Field::PerceptionInput {
+                data_type, name, ..
+            }
+            | Field::RequiredInput {
+                data_type, name, ..
+            } => (name, data_type),
+            _ => panic!("unexpected field {field:?}"),
+        };
+        let assertion_name = format_ident!("cross_input_{}_must_be_serializable", name);
+        quote! {
+            const _: fn() = || {
+                fn #assertion_name<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+                #assertion_name::<#data_type>();
+            };
         }
+    });
+    quote! {
+        #(#assertions)*
     }
 }
 
@@ -93,6 +280,14 @@ fn generate_struct(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
         }
     };
     let node_fields = generate_node_fields(cycler);
+    let cycle_budget_fields = if cycler.enable_cycle_budget {
+        quote! {
+            cycle_budget_overruns: usize,
+            last_cycle_budget_warning: Option<std::time::Instant>,
+        }
+    } else {
+        Default::default()
+    };
 
     quote! {
         pub(crate) struct Cycler<HardwareInterface>  {
@@ -108,6 +303,12 @@ fn generate_struct(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             #node_fields
             recording_sender: std::sync::mpsc::SyncSender<crate::cyclers::RecordingFrame>,
             enable_recording: bool,
+            enable_instrumentation: bool,
+            body_id: String,
+            head_id: String,
+            is_recording: bool,
+            #cycle_budget_fields
+            recording_frame_capacity: usize,
         }
     }
 }
@@ -170,15 +371,31 @@ fn generate_node_fields(cycler: &Cycler) -> TokenStream {
             }
         })
         .collect();
+    let missing_required_inputs_counters = cycler
+        .iter_nodes()
+        .filter(|node| node.has_required_inputs())
+        .map(|node| {
+            let counter = missing_required_inputs_counter(node);
+            quote! {
+                #counter: usize
+            }
+        });
     quote! {
         #(#fields,)*
+        #(#missing_required_inputs_counters,)*
     }
 }
 
+fn missing_required_inputs_counter(node: &Node) -> Ident {
+    format_ident!("{}_missing_required_inputs", node.name.to_case(Case::Snake))
+}
+
 fn generate_implementation(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     let new_method = generate_new_method(cycler, cyclers);
     let start_method = generate_start_method();
     let cycle_method = generate_cycle_method(cycler, cyclers);
+    let record_header_method = generate_record_header_method(cycler);
+    let reset_databases_method = generate_reset_databases_method(cycler);
 
     quote! {
         impl<HardwareInterface> Cycler<HardwareInterface>
@@ -188,10 +405,51 @@ fn generate_implementation(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             #new_method
             #start_method
             #cycle_method
+            #record_header_method
+            #reset_databases_method
+        }
+    }
+}
+
+fn generate_record_header_method(cycler: &Cycler) -> TokenStream {
+    let recording_variants = generate_recording_variants(cycler, quote! { Header });
+
+    quote! {
+        fn record_header(&self) -> color_eyre::Result<()> {
+            let instance = self.instance;
+            let parameters = self.parameters_reader.next();
+            let mut recording_frame = Vec::new();
+            RecordingEncoder::serialize_value(
+                &mut recording_frame,
+                &crate::cyclers::RecordingHeader {
+                    body_id: &self.body_id,
+                    head_id: &self.head_id,
+                    parameters: &parameters,
+                },
+            )
+            .wrap_err("failed to record header")?;
+            let timestamp = <HardwareInterface as hardware::TimeInterface>::get_now(&*self.hardware_interface);
+            self.recording_sender.try_send(match instance {
+                #(#recording_variants)*
+            }).wrap_err("failed to send recording header")
         }
     }
 }
 
+fn generate_reset_databases_method(cycler: &Cycler) -> TokenStream {
+    match cycler.kind {
+        CyclerKind::Perception => Default::default(),
+        CyclerKind::RealTime => quote! {
+            /// Drops all historic and perception databases, e.g. to continue with a discontinuous
+            /// scenario without reconstructing the cycler
+            pub(crate) fn reset_databases(&mut self) {
+                self.historic_databases = Default::default();
+                self.perception_databases = Default::default();
+            }
+        },
+    }
+}
+
 fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     let input_output_fields = generate_input_output_fields(cycler, cyclers);
     let cycler_module_name = format_ident!("{}", cycler.name.to_case(Case::Snake));
@@ -200,6 +458,18 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
         .iter_nodes()
         .map(|node| format_ident!("{}", node.name.to_case(Case::Snake)));
     let input_output_identifiers = generate_input_output_identifiers(cycler, cyclers);
+    let missing_required_inputs_counters = cycler
+        .iter_nodes()
+        .filter(|node| node.has_required_inputs())
+        .map(missing_required_inputs_counter);
+    let cycle_budget_initializers = if cycler.enable_cycle_budget {
+        quote! {
+            cycle_budget_overruns: 0,
+            last_cycle_budget_warning: None,
+        }
+    } else {
+        Default::default()
+    };
 
     quote! {
         pub(crate) fn new(
@@ -212,6 +482,9 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             #input_output_fields
             recording_sender: std::sync::mpsc::SyncSender<crate::cyclers::RecordingFrame>,
             enable_recording: bool,
+            enable_instrumentation: bool,
+            body_id: &str,
+            head_id: &str,
         ) -> color_eyre::Result<Self> {
             let parameters = parameters_reader.next().clone();
             let mut cycler_state = crate::structs::#cycler_module_name::CyclerState::default();
@@ -226,8 +499,15 @@ fn generate_new_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                 cycler_state,
                 #input_output_identifiers
                 #(#node_identifiers,)*
+                #(#missing_required_inputs_counters: 0,)*
                 recording_sender,
                 enable_recording,
+                enable_instrumentation,
+                body_id: body_id.to_string(),
+                head_id: head_id.to_string(),
+                is_recording: false,
+                #cycle_budget_initializers
+                recording_frame_capacity: 0,
             })
         }
     }
@@ -379,6 +659,9 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     let cross_inputs = get_cross_inputs(cycler);
     let cross_input_recordings = generate_cross_inputs_recording(cycler, cross_inputs);
 
+    let time_serialization = generate_timed_recording_serialization(quote! {
+        RecordingEncoder::serialize_value(&mut recording_frame, &now).wrap_err("failed to record time")?;
+    });
     let post_setup = match cycler.kind {
         CyclerKind::Perception => quote! {
             self.own_producer.announce();
@@ -392,7 +675,7 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                     #perception_cycler_updates
                 });
                 if enable_recording {
-                    bincode::serialize_into(&mut recording_frame, &now).wrap_err("failed to record time")?;
+                    #time_serialization
                 }
             }
         }
@@ -423,12 +706,15 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             );
         },
     };
-    let recording_variants = cycler.instances.iter().map(|instance| {
-        let instance_name = format_ident!("{}", instance);
-        quote! {
-            CyclerInstance::#instance_name => crate::cyclers::RecordingFrame::#instance_name { data: recording_frame },
-        }
-    });
+    let recording_decision = format_ident!("{}", RECORDING_DECISION_PATH);
+    let recording_decision_path_string = format!("additional_outputs.{RECORDING_DECISION_PATH}");
+    let recording_trigger = format_ident!("{}", RECORDING_TRIGGER_PATH);
+    let recording_trigger_path_string = format!("additional_outputs.{RECORDING_TRIGGER_PATH}");
+    let recording_serialization_time = format_ident!("{}", RECORDING_SERIALIZATION_TIME_PATH);
+    let recording_serialization_time_path_string =
+        format!("additional_outputs.{RECORDING_SERIALIZATION_TIME_PATH}");
+    let (cycle_start, cycle_budget_check) = generate_cycle_budget_check(cycler);
+    let recording_variants = generate_recording_variants(cycler, quote! { Cycle });
 
     quote! {
         #[allow(clippy::nonminimal_bool)]
@@ -436,7 +722,9 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
             {
                 let instance = self.instance;
                 let instance_name = format!("{instance:?}");
-                let itt_domain = ittapi::Domain::new(&instance_name);
+                let itt_domain = self
+                    .enable_instrumentation
+                    .then(|| ittapi::Domain::new(&instance_name));
 
                 let mut own_database = self.own_writer.next();
                 let own_database_reference = {
@@ -444,8 +732,40 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                     own_database.deref_mut()
                 };
 
-                let enable_recording = self.enable_recording && self.hardware_interface.should_record();
-                let mut recording_frame = Vec::new(); // TODO: possible optimization: cache capacity
+                // subscribing exactly to the trigger path starts a recording without changing parameters
+                let enabled_by_trigger = self
+                    .own_subscribed_outputs_reader
+                    .next()
+                    .contains(#recording_trigger_path_string);
+                let enabled_by_configuration = self.enable_recording;
+                let enabled_by_hardware = self.hardware_interface.should_record();
+                let enable_recording =
+                    (enabled_by_configuration || enabled_by_trigger) && enabled_by_hardware;
+                // every recording starts with a header, also when it is started while running
+                if enable_recording && !self.is_recording {
+                    self.record_header()?;
+                }
+                self.is_recording = enable_recording;
+                // frames of consecutive cycles have similar sizes, reserve the size of the last one
+                // upfront to avoid reallocations while serializing
+                let mut recording_frame = Vec::with_capacity(self.recording_frame_capacity);
+                let mut recording_serialization_time = std::time::Duration::ZERO;
+
+                {
+                    let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
+                    framework::AdditionalOutput::new(enabled_by_trigger, &mut own_database_reference.additional_outputs.#recording_trigger)
+                        .fill_if_subscribed(|| enable_recording);
+                    let is_subscribed = own_subscribed_outputs
+                        .iter()
+                        .any(|subscribed_output| framework::should_be_filled(subscribed_output, #recording_decision_path_string));
+                    let recording_decision = &mut own_database_reference.additional_outputs.#recording_decision;
+                    framework::AdditionalOutput::new(is_subscribed, &mut recording_decision.enabled_by_configuration)
+                        .fill_if_subscribed(|| enabled_by_configuration);
+                    framework::AdditionalOutput::new(is_subscribed, &mut recording_decision.enabled_by_hardware)
+                        .fill_if_subscribed(|| enabled_by_hardware);
+                    framework::AdditionalOutput::new(is_subscribed, &mut recording_decision.enabled_by_trigger)
+                        .fill_if_subscribed(|| enabled_by_trigger);
+                }
 
                 {
                     let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
@@ -453,6 +773,8 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
                     #(#setup_node_executions)*
                 }
 
+                #cycle_start
+
                 #post_setup
 
                 {
@@ -465,7 +787,20 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
 
                 #after_remaining_nodes
 
+                #cycle_budget_check
+
+                {
+                    let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
+                    let is_subscribed = own_subscribed_outputs
+                        .iter()
+                        .any(|subscribed_output| framework::should_be_filled(subscribed_output, #recording_serialization_time_path_string));
+                    framework::AdditionalOutput::new(is_subscribed, &mut own_database_reference.additional_outputs.#recording_serialization_time)
+                        .mutate_if_subscribed(|data| *data = enable_recording.then_some(recording_serialization_time));
+                }
+
                 if enable_recording {
+                    self.recording_frame_capacity = recording_frame.len();
+                    let timestamp = <HardwareInterface as hardware::TimeInterface>::get_now(&*self.hardware_interface);
                     self.recording_sender.try_send(match instance {
                         #(#recording_variants)*
                     }).wrap_err("failed to send recording frame")?;
@@ -477,7 +812,81 @@ fn generate_cycle_method(cycler: &Cycler, cyclers: &Cyclers) -> TokenStream {
     }
 }
 
-fn get_cross_inputs(cycler: &Cycler) -> HashSet<Field> {
+fn generate_cycle_budget_check(cycler: &Cycler) -> (TokenStream, TokenStream) {
+    if !cycler.enable_cycle_budget {
+        return Default::default();
+    }
+    let cycle_budget = format_ident!("{}", CYCLE_BUDGET_PATH);
+    let cycle_budget_overruns = format_ident!("{}", CYCLE_BUDGET_OVERRUNS_PATH);
+    let cycle_budget_overruns_path_string =
+        format!("additional_outputs.{CYCLE_BUDGET_OVERRUNS_PATH}");
+    let cycle_budget_variants = cycler.instances.iter().map(|instance| {
+        let instance_name = format_ident!("{}", instance);
+        let parameter_name = format_ident!("{}", instance.to_case(Case::Snake));
+        quote! {
+            CyclerInstance::#instance_name => parameters.#cycle_budget.#parameter_name,
+        }
+    });
+
+    // measured after the setup nodes, which mostly wait for new data (e.g. from the hardware
+    // interface), so the budget only covers the processing of the cycle nodes
+    let cycle_start = quote! {
+        let cycle_start = std::time::Instant::now();
+    };
+    let cycle_budget_check = quote! {
+        {
+            let cycle_end = std::time::Instant::now();
+            let cycle_duration = cycle_end.duration_since(cycle_start);
+            let parameters = self.parameters_reader.next();
+            let cycle_budget = match instance {
+                #(#cycle_budget_variants)*
+            };
+            if cycle_duration > cycle_budget {
+                self.cycle_budget_overruns += 1;
+                // overruns come in bursts, warn at most once per second to not flood the log
+                let has_warned_recently = matches!(
+                    self.last_cycle_budget_warning,
+                    Some(last_warning) if cycle_end.duration_since(last_warning) < std::time::Duration::from_secs(1)
+                );
+                if !has_warned_recently {
+                    log::warn!(
+                        "{instance_name} exceeded cycle budget of {cycle_budget:?}: {cycle_duration:?} ({} overruns in total)",
+                        self.cycle_budget_overruns,
+                    );
+                    self.last_cycle_budget_warning = Some(cycle_end);
+                }
+            }
+        }
+        {
+            let own_subscribed_outputs = self.own_subscribed_outputs_reader.next();
+            let is_subscribed = own_subscribed_outputs
+                .iter()
+                .any(|subscribed_output| framework::should_be_filled(subscribed_output, #cycle_budget_overruns_path_string));
+            framework::AdditionalOutput::new(is_subscribed, &mut own_database_reference.additional_outputs.#cycle_budget_overruns)
+                .fill_if_subscribed(|| self.cycle_budget_overruns);
+        }
+    };
+    (cycle_start, cycle_budget_check)
+}
+
+fn generate_recording_variants(cycler: &Cycler, kind: TokenStream) -> Vec<TokenStream> {
+    cycler
+        .instances
+        .iter()
+        .map(|instance| {
+            let instance_name = format_ident!("{}", instance);
+            quote! {
+                CyclerInstance::#instance_name => crate::cyclers::RecordingFrame::#instance_name {
+                    kind: crate::cyclers::RecordingFrameKind::#kind,
+                    timestamp,
+                    data: recording_frame,
+                },
+            }
+        })
+        .collect()
+}
+
+fn get_cross_inputs(cycler: &Cycler) -> Vec<Field> {
     cycler
         .setup_nodes
         .iter()
@@ -503,9 +912,214 @@ fn get_cross_inputs(cycler: &Cycler) -> HashSet<Field> {
                 })
                 .cloned()
         })
+        .unique()
         .collect()
 }
 
+struct RecordedValue {
+    path: String,
+    data_type: TokenStream,
+    is_node_state: bool,
+}
+
+fn recorded_values(cycler: &Cycler) -> Vec<RecordedValue> {
+    // Mirrors the serialization order of `cycle()`: setup node states with their main outputs,
+    // the time of real-time cyclers, cross inputs and finally the cycle node states
+    let value = |path: String, data_type: TokenStream| RecordedValue {
+        path,
+        data_type,
+        is_node_state: false,
+    };
+    let node_state = |node: &Node| {
+        let node_module = &node.module;
+        let node_name = format_ident!("{}", node.name);
+        RecordedValue {
+            path: node.name.clone(),
+            data_type: quote! { #node_module::#node_name },
+            is_node_state: true,
+        }
+    };
+    let setup_values = cycler.setup_nodes.iter().flat_map(|node| {
+        once(node_state(node)).chain(node.contexts.main_outputs.iter().filter_map(|field| {
+            match field {
+                Field::MainOutput { data_type, name } => {
+                    Some(value(format!("main_outputs.{name}"), quote! { #data_type }))
+                }
+                _ => None,
+            }
+        }))
+    });
+    let time_value = match cycler.kind {
+        CyclerKind::Perception => None,
+        CyclerKind::RealTime => Some(value("now".to_string(), quote! { std::time::SystemTime })),
+    };
+    let cross_input_values = get_cross_inputs(cycler)
+        .into_iter()
+        .map(|field| match field {
+            Field::CyclerState {
+                data_type, path, ..
+            } => value(
+                format!("cycler_state.{}", path_to_string(&path)),
+                quote! { #data_type },
+            ),
+            Field::Input {
+                cycler_instance: Some(cycler_instance),
+                data_type,
+                path,
+                ..
+            } => value(
+                format!(
+                    "{}.main_outputs.{}",
+                    cycler_instance.to_case(Case::Snake),
+                    path_to_string(&path)
+                ),
+                quote! { #data_type },
+            ),
+            Field::RequiredInput {
+                cycler_instance: Some(cycler_instance),
+                data_type,
+                path,
+                ..
+            } => {
+                // Required inputs are only recorded once unwrapped
+                let data_type = unwrap_option_type(data_type);
+                value(
+                    format!(
+                        "{}.main_outputs.{}",
+                        cycler_instance.to_case(Case::Snake),
+                        path_to_string(&path)
+                    ),
+                    quote! { #data_type },
+                )
+            }
+            Field::PerceptionInput {
+                cycler_instance,
+                data_type,
+                path,
+                ..
+            } => value(
+                format!(
+                    "{}.main_outputs.{}",
+                    cycler_instance.to_case(Case::Snake),
+                    path_to_string(&path)
+                ),
+                quote! { [std::collections::BTreeMap<std::time::SystemTime, Vec<#data_type>>; 2] },
+            ),
+            _ => panic!("unexpected field {field:?}"),
+        });
+    let cycle_values = cycler.cycle_nodes.iter().map(node_state);
+    setup_values
+        .chain(time_value)
+        .chain(cross_input_values)
+        .chain(cycle_values)
+        .collect()
+}
+
+fn generate_recorded_fields(cycler: &Cycler) -> TokenStream {
+    let recorded_fields = recorded_values(cycler).into_iter().map(
+        |RecordedValue {
+             path,
+             data_type,
+             is_node_state,
+         }| {
+            let data_type = tokens_to_string(data_type);
+            quote! {
+                crate::cyclers::RecordedField {
+                    path: #path,
+                    data_type: #data_type,
+                    is_node_state: #is_node_state,
+                },
+            }
+        },
+    );
+
+    quote! {
+        /// Lists the values contained in every cycle frame of a recording of this cycler
+        pub(crate) fn recorded_fields() -> &'static [crate::cyclers::RecordedField] {
+            &[
+                #(#recorded_fields)*
+            ]
+        }
+    }
+}
+
+fn generate_recording_frame_decoder(cycler: &Cycler) -> TokenStream {
+    let decoded_values = recorded_values(cycler).into_iter().map(
+        |RecordedValue {
+             path,
+             data_type,
+             is_node_state,
+         }| {
+            let error_message = format!("failed to decode `{path}`");
+            let decoded_value = if is_node_state {
+                quote! { crate::cyclers::skip_node_state(&mut data) }
+            } else {
+                quote! {
+                    crate::cyclers::decode_recorded_value::<RecordingEncoder, #data_type>(&mut data)
+                }
+            };
+            quote! {
+                values.insert(
+                    #path.to_string(),
+                    #decoded_value.wrap_err(#error_message)?,
+                );
+            }
+        },
+    );
+
+    quote! {
+        /// Decodes the data of one cycle frame of this cycler (without its leading kind) into JSON
+        /// for inspection, keyed by the paths of `recorded_fields()`, node states are replaced by
+        /// placeholders
+        pub(crate) fn decode_recording_frame(mut data: &[u8]) -> color_eyre::Result<serde_json::Value> {
+            let mut values = serde_json::Map::new();
+            #(#decoded_values)*
+            Ok(serde_json::Value::Object(values))
+        }
+    }
+}
+
+fn generate_node_descriptions(cycler: &Cycler) -> TokenStream {
+    let setup_nodes = cycler.setup_nodes.iter().map(|node| (node, true));
+    let cycle_nodes = cycler.cycle_nodes.iter().map(|node| (node, false));
+    let node_descriptions = setup_nodes.chain(cycle_nodes).map(|(node, is_setup_node)| {
+        let name = &node.name;
+        let node_module = &node.module;
+        let module = tokens_to_string(quote! { #node_module });
+        quote! {
+            crate::cyclers::NodeDescription {
+                name: #name,
+                module: #module,
+                is_setup_node: #is_setup_node,
+            },
+        }
+    });
+
+    quote! {
+        /// Lists the nodes of this cycler in execution order
+        pub(crate) fn nodes() -> &'static [crate::cyclers::NodeDescription] {
+            &[
+                #(#node_descriptions)*
+            ]
+        }
+    }
+}
+
+fn tokens_to_string(tokens: TokenStream) -> String {
+    tokens.to_string().replace(' ', "")
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| {
+            let variable_prefix = if segment.is_variable { "$" } else { "" };
+            let optional_suffix = if segment.is_optional { "?" } else { "" };
+            format!("{variable_prefix}{}{optional_suffix}", segment.name)
+        })
+        .join(".")
+}
+
 fn generate_cross_inputs_recording(
     cycler: &Cycler,
     cross_inputs: impl IntoIterator<Item = Field>,
@@ -606,9 +1220,9 @@ fn generate_cross_inputs_recording(
             }
             _ => panic!("unexpected field {field:?}"),
         };
-        quote! {
-            bincode::serialize_into(&mut recording_frame, #value_to_be_recorded).wrap_err(#error_message)?;
-        }
+        generate_timed_recording_serialization(quote! {
+            RecordingEncoder::serialize_value(&mut recording_frame, #value_to_be_recorded).wrap_err(#error_message)?;
+        })
     }).collect::<Vec<_>>();
 
     if recordings.is_empty() {
@@ -622,6 +1236,16 @@ fn generate_cross_inputs_recording(
     }
 }
 
+fn generate_timed_recording_serialization(serialization: TokenStream) -> TokenStream {
+    quote! {
+        {
+            let recording_serialization_start = std::time::Instant::now();
+            #serialization
+            recording_serialization_time += recording_serialization_start.elapsed();
+        }
+    }
+}
+
 fn generate_perception_cycler_updates(cyclers: &Cyclers) -> TokenStream {
     cyclers
         .instances_with(CyclerKind::Perception)
@@ -648,32 +1272,103 @@ fn generate_node_execution(
     let recording_error_message = format!("failed to record `{}`", node.name);
     let cycle_error_message = format!("failed to execute cycle of `{}`", node.name);
     let database_updates = generate_database_updates(node, recording_generation);
-    let database_updates_from_defaults = generate_database_updates_from_defaults(node);
+    let database_updates_from_defaults =
+        generate_database_updates_from_defaults(node, recording_generation);
+    let (missing_required_inputs_increment, missing_required_inputs_update) =
+        generate_missing_required_inputs_update(node);
+    let node_serialization = generate_timed_recording_serialization(quote! {
+        framework::write_length_prefixed(&mut recording_frame, |recording_frame| {
+            RecordingEncoder::serialize_value(recording_frame, &self.#node_member)
+        })
+        .wrap_err(#recording_error_message)?;
+    });
+    let node_context = quote! {
+        #node_module::CycleContext::new(
+            #context_initializers
+        )
+    };
+    let node_cycle = |context: TokenStream| {
+        let node_cycle = quote! {
+            {
+                let _task = itt_domain
+                    .as_ref()
+                    .map(|itt_domain| ittapi::Task::begin(itt_domain, #node_name));
+                self.#node_member.cycle(#context)
+                    .wrap_err(#cycle_error_message)?
+            }
+        };
+        // Nodes without main outputs only act via side effects (e.g. on the cycler state), their
+        // empty `MainOutputs` are discarded instead of binding them for no database update
+        if node.contexts.main_outputs.is_empty() {
+            quote! {
+                #node_cycle;
+            }
+        } else {
+            quote! {
+                let main_outputs = #node_cycle;
+                #database_updates
+            }
+        }
+    };
+    let guarded_node_cycle = if node.has_cycle_guard {
+        let node_type = format_ident!("{}", node.name);
+        let node_cycle = node_cycle(quote! { context });
+        quote! {
+            let context = #node_context;
+            if #node_module::#node_type::should_cycle(&context) {
+                #node_cycle
+            } else {
+                #database_updates_from_defaults
+            }
+        }
+    } else {
+        node_cycle(node_context)
+    };
     quote! {
         {
             if enable_recording {
-                bincode::serialize_into(&mut recording_frame, &self.#node_member).wrap_err(#recording_error_message)?;
+                #node_serialization
             }
             #[allow(clippy::needless_else)]
             if #are_required_inputs_some {
-                let main_outputs = {
-                    let _task = ittapi::Task::begin(&itt_domain, #node_name);
-                    self.#node_member.cycle(
-                        #node_module::CycleContext::new(
-                            #context_initializers
-                        ),
-                    )
-                    .wrap_err(#cycle_error_message)?
-                };
-                #database_updates
+                #guarded_node_cycle
             }
             else {
                 #database_updates_from_defaults
+                #missing_required_inputs_increment
             }
+            #missing_required_inputs_update
         }
     }
 }
 
+fn generate_missing_required_inputs_update(node: &Node) -> (TokenStream, TokenStream) {
+    if !node.has_required_inputs() {
+        return Default::default();
+    }
+    let counter = missing_required_inputs_counter(node);
+    let missing_required_inputs = format_ident!("{}", MISSING_REQUIRED_INPUTS_PATH);
+    let node_member = format_ident!("{}", node.name.to_case(Case::Snake));
+    let path_string = format!(
+        "additional_outputs.{MISSING_REQUIRED_INPUTS_PATH}.{}",
+        node.name.to_case(Case::Snake)
+    );
+    let increment = quote! {
+        self.#counter += 1;
+    };
+    let update = quote! {
+        {
+            let is_subscribed = own_subscribed_outputs
+                .iter()
+                .any(|subscribed_output| framework::should_be_filled(subscribed_output, #path_string));
+            framework::AdditionalOutput::new(is_subscribed, &mut own_database_reference.additional_outputs.#missing_required_inputs.#node_member)
+                .fill_if_subscribed(|| self.#counter);
+        }
+    };
+    (increment, update)
+}
+
+#[derive(Clone, Copy)]
 enum RecordingGeneration {
     Generate,
     Skip,
@@ -839,6 +1534,9 @@ fn generate_context_initializers(node: &Node, cycler: &Cycler) -> TokenStream {
                         ReferenceKind::Immutable,
                         cycler,
                     );
+                    // built in every cycle, not only while recording: the maps borrow from the
+                    // perception databases of this cycle and therefore cannot be kept and reused
+                    // across cycles
                     quote! {
                         framework::PerceptionInput {
                             persistent: self
@@ -912,11 +1610,16 @@ fn generate_database_updates(
             Field::MainOutput { name, .. } => {
                 let error_message = format!("failed to record {name}");
                 let recording_serialization = match recording_generation {
-                    RecordingGeneration::Generate => quote! {
-                        if enable_recording {
-                            bincode::serialize_into(&mut recording_frame, &main_outputs.#name.value).wrap_err(#error_message)?;
+                    RecordingGeneration::Generate => {
+                        let main_output_serialization = generate_timed_recording_serialization(quote! {
+                            RecordingEncoder::serialize_value(&mut recording_frame, &main_outputs.#name.value).wrap_err(#error_message)?;
+                        });
+                        quote! {
+                            if enable_recording {
+                                #main_output_serialization
+                            }
                         }
-                    },
+                    }
                     RecordingGeneration::Skip => Default::default(),
                 };
                 let setter = quote! {
@@ -930,14 +1633,33 @@ fn generate_database_updates(
         .collect()
 }
 
-fn generate_database_updates_from_defaults(node: &Node) -> TokenStream {
+fn generate_database_updates_from_defaults(
+    node: &Node,
+    recording_generation: RecordingGeneration,
+) -> TokenStream {
+    // Defaulted main outputs are recorded as well to keep the layout of recording frames fixed
     node.contexts
         .main_outputs
         .iter()
         .filter_map(|field| match field {
             Field::MainOutput { name, .. } => {
+                let error_message = format!("failed to record {name}");
+                let recording_serialization = match recording_generation {
+                    RecordingGeneration::Generate => {
+                        let main_output_serialization = generate_timed_recording_serialization(quote! {
+                            RecordingEncoder::serialize_value(&mut recording_frame, &own_database_reference.main_outputs.#name).wrap_err(#error_message)?;
+                        });
+                        quote! {
+                            if enable_recording {
+                                #main_output_serialization
+                            }
+                        }
+                    }
+                    RecordingGeneration::Skip => Default::default(),
+                };
                 let setter = quote! {
                     own_database_reference.main_outputs.#name = Default::default();
+                    #recording_serialization
                 };
                 Some(setter)
             }
@@ -945,3 +1667,269 @@ fn generate_database_updates_from_defaults(node: &Node) -> TokenStream {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use source_analyzer::contexts::Contexts;
+    use syn::{parse_quote, Block, Expr, GenericArgument, ItemFn, PathArguments, Stmt, Type};
+
+    use super::*;
+
+    fn node_from_source(name: &str, has_cycle_guard: bool, source: &str) -> Node {
+        let file = syn::parse_str(source).unwrap();
+        Node {
+            name: name.to_string(),
+            module: syn::parse_str(&format!("control::{}", name.to_case(Case::Snake))).unwrap(),
+            file_path: PathBuf::new(),
+            contexts: Contexts::try_from_file(&file).unwrap(),
+            has_cycle_guard,
+        }
+    }
+
+    fn cycler_with_node(node: &Node) -> Cycler {
+        Cycler {
+            name: "Control".to_string(),
+            kind: CyclerKind::Perception,
+            instances: vec!["Control".to_string()],
+            setup_nodes: vec![],
+            cycle_nodes: vec![node.clone()],
+            enable_cycle_budget: false,
+            recording_encoder: syn::parse_quote!(crate::cyclers::BincodeEncoder),
+        }
+    }
+
+    fn parsed_node_execution(node: &Node, cycler: &Cycler) -> Block {
+        syn::parse2(generate_node_execution(
+            node,
+            cycler,
+            RecordingGeneration::Skip,
+        ))
+        .unwrap()
+    }
+
+    /// Flattens all expressions of the statements (including nested ones) in source order
+    fn nested_expressions(statements: &[Stmt]) -> Vec<&Expr> {
+        let mut expressions = Vec::new();
+        for statement in statements {
+            match statement {
+                Stmt::Local(local) => {
+                    if let Some((_, initializer)) = &local.init {
+                        collect_expressions(initializer, &mut expressions);
+                    }
+                }
+                Stmt::Expr(expression) | Stmt::Semi(expression, _) => {
+                    collect_expressions(expression, &mut expressions)
+                }
+                Stmt::Item(_) => {}
+            }
+        }
+        expressions
+    }
+
+    fn collect_expressions<'expression>(
+        expression: &'expression Expr,
+        expressions: &mut Vec<&'expression Expr>,
+    ) {
+        expressions.push(expression);
+        match expression {
+            Expr::Assign(assignment) => {
+                collect_expressions(&assignment.left, expressions);
+                collect_expressions(&assignment.right, expressions);
+            }
+            Expr::Block(block) => expressions.extend(nested_expressions(&block.block.stmts)),
+            Expr::Call(call) => {
+                collect_expressions(&call.func, expressions);
+                for argument in &call.args {
+                    collect_expressions(argument, expressions);
+                }
+            }
+            Expr::If(if_expression) => {
+                collect_expressions(&if_expression.cond, expressions);
+                expressions.extend(nested_expressions(&if_expression.then_branch.stmts));
+                if let Some((_, else_branch)) = &if_expression.else_branch {
+                    collect_expressions(else_branch, expressions);
+                }
+            }
+            Expr::MethodCall(method_call) => {
+                collect_expressions(&method_call.receiver, expressions);
+                for argument in &method_call.args {
+                    collect_expressions(argument, expressions);
+                }
+            }
+            Expr::Reference(reference) => collect_expressions(&reference.expr, expressions),
+            Expr::Try(try_expression) => collect_expressions(&try_expression.expr, expressions),
+            _ => {}
+        }
+    }
+
+    fn cycles(expression: &Expr, node_member: Expr, context: Expr) -> bool {
+        matches!(
+            expression,
+            Expr::MethodCall(method_call)
+                if method_call.method == "cycle"
+                    && *method_call.receiver == node_member
+                    && method_call.args.iter().eq([&context])
+        )
+    }
+
+    #[test]
+    fn nodes_without_main_outputs_only_cycle_and_record_their_state() {
+        let node = node_from_source(
+            "SideEffect",
+            false,
+            r#"
+                #[context]
+                pub struct CreationContext {}
+
+                #[context]
+                pub struct CycleContext {
+                    counter: CyclerState<usize, "counter">,
+                }
+
+                #[context]
+                pub struct MainOutputs {}
+            "#,
+        );
+        let cycler = cycler_with_node(&node);
+
+        let node_execution = parsed_node_execution(&node, &cycler);
+        let expressions = nested_expressions(&node_execution.stmts);
+        assert_eq!(
+            expressions
+                .iter()
+                .filter(|expression| cycles(
+                    expression,
+                    parse_quote! { self.side_effect },
+                    parse_quote! {
+                        control::side_effect::CycleContext::new(&mut self.cycler_state.counter,)
+                    },
+                ))
+                .count(),
+            1
+        );
+        assert!(!expressions
+            .iter()
+            .any(|expression| matches!(expression, Expr::Assign(_))));
+
+        let recorded_paths: Vec<_> = recorded_values(&cycler)
+            .into_iter()
+            .map(|recorded_value| recorded_value.path)
+            .collect();
+        assert_eq!(recorded_paths, ["cycler_state.counter", "SideEffect"]);
+    }
+
+    #[test]
+    fn guarded_nodes_default_their_main_outputs_if_the_guard_is_false() {
+        let node = node_from_source(
+            "Guarded",
+            true,
+            r#"
+                #[context]
+                pub struct CreationContext {}
+
+                #[context]
+                pub struct CycleContext {
+                    game_state: Input<GameState, "game_state">,
+                }
+
+                #[context]
+                pub struct MainOutputs {
+                    pub value: MainOutput<usize>,
+                }
+            "#,
+        );
+        let cycler = cycler_with_node(&node);
+
+        let node_execution = parsed_node_execution(&node, &cycler);
+        let guard_condition: Expr = parse_quote! {
+            control::guarded::Guarded::should_cycle(&context)
+        };
+        let guard = nested_expressions(&node_execution.stmts)
+            .into_iter()
+            .find_map(|expression| match expression {
+                Expr::If(if_expression) if *if_expression.cond == guard_condition => {
+                    Some(if_expression)
+                }
+                _ => None,
+            })
+            .expect("cycle of guarded node has to be conditioned on its guard");
+
+        assert!(nested_expressions(&guard.then_branch.stmts)
+            .into_iter()
+            .any(|expression| cycles(
+                expression,
+                parse_quote! { self.guarded },
+                parse_quote! { context },
+            )));
+        let (_, else_branch) = guard
+            .else_branch
+            .as_ref()
+            .expect("guarded node has to default its main outputs");
+        let expected_else_branch: Expr = parse_quote! {
+            {
+                own_database_reference.main_outputs.value = Default::default();
+            }
+        };
+        assert_eq!(**else_branch, expected_else_branch);
+    }
+
+    #[test]
+    fn recording_frame_decoder_skips_node_states() {
+        let node = node_from_source(
+            "BallDetection",
+            false,
+            r#"
+                pub struct BallDetection {
+                    #[serde(skip, default = "deserialize_not_implemented")]
+                    neural_networks: NeuralNetworks,
+                }
+
+                #[context]
+                pub struct CreationContext {}
+
+                #[context]
+                pub struct CycleContext {}
+
+                #[context]
+                pub struct MainOutputs {
+                    pub balls: MainOutput<Option<Vec<Ball>>>,
+                }
+            "#,
+        );
+        let cycler = cycler_with_node(&node);
+
+        let decoder: ItemFn = syn::parse2(generate_recording_frame_decoder(&cycler)).unwrap();
+        let expressions = nested_expressions(&decoder.block.stmts);
+        let skipped_node_state: Expr = parse_quote! {
+            crate::cyclers::skip_node_state(&mut data)
+        };
+        assert!(expressions
+            .iter()
+            .any(|expression| **expression == skipped_node_state));
+        let node_type: Type = parse_quote! { control::ball_detection::BallDetection };
+        let deserializes_node_type = |expression: &&Expr| {
+            match expression {
+            Expr::Path(path) => path.path.segments.iter().any(|segment| {
+                matches!(
+                    &segment.arguments,
+                    PathArguments::AngleBracketed(arguments)
+                        if arguments.args.iter().any(|argument| {
+                            matches!(argument, GenericArgument::Type(data_type) if *data_type == node_type)
+                        })
+                )
+            }),
+            _ => false,
+        }
+        };
+        assert!(!expressions.iter().any(deserializes_node_type));
+
+        let recorded_node_states: Vec<_> = recorded_values(&cycler)
+            .into_iter()
+            .filter(|recorded_value| recorded_value.is_node_state)
+            .map(|recorded_value| recorded_value.path)
+            .collect();
+        assert_eq!(recorded_node_states, ["BallDetection"]);
+    }
+}
@@ -3,7 +3,7 @@ use std::iter::repeat;
 use convert_case::{Case, Casing};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use source_analyzer::cyclers::{CyclerKind, Cyclers};
+use source_analyzer::cyclers::{Cycler, CyclerKind, Cyclers};
 
 pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
     let construct_multiple_buffers = generate_multiple_buffers(cyclers);
@@ -25,6 +25,7 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
             head_id: String,
             keep_running: tokio_util::sync::CancellationToken,
             cycler_instances_to_be_recorded: std::collections::HashSet<String>,
+            enable_instrumentation: bool,
         ) -> color_eyre::Result<()>
         {
             use color_eyre::eyre::WrapErr;
@@ -34,7 +35,7 @@ pub fn generate_run_function(cyclers: &Cyclers) -> TokenStream {
             let (recording_sender, recording_receiver) = std::sync::mpsc::sync_channel(420);
 
             let communication_server = communication::server::Runtime::start(
-                addresses, parameters_directory, body_id, head_id, #number_of_parameter_slots, keep_running.clone())
+                addresses, parameters_directory, body_id.clone(), head_id.clone(), #number_of_parameter_slots, keep_running.clone())
                 .wrap_err("failed to start communication server")?;
 
             let recording_thread = #recording_thread;
@@ -117,20 +118,35 @@ fn generate_future_queues(cyclers: &Cyclers) -> TokenStream {
 }
 
 fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
-    let file_creations = cyclers.instances().map(|(_cycler, instance)| {
+    let file_creations = cyclers.instances().map(|(cycler, instance)| {
         let instance_name_snake_case = format_ident!("{}", instance.to_case(Case::Snake));
-        let recording_file_path = format!("logs/{instance}.{{seconds}}.bincode");
+        let index_identifier = format_ident!("{}_index", instance.to_case(Case::Snake));
+        let encoder = recording_encoder(cycler);
+        let recording_file_path = format!("logs/{instance}.{{seconds}}.{{extension}}");
+        let index_file_path = format!("logs/{instance}.{{seconds}}.index.{{extension}}");
         let error_message = format!("failed to create recording file for {instance}");
+        let index_error_message = format!("failed to create recording index file for {instance}");
         quote! {
+            let extension = <#encoder as crate::cyclers::FrameEncoder>::FILE_EXTENSION;
             let mut #instance_name_snake_case = std::io::BufWriter::new(std::fs::File::create(format!(#recording_file_path)).wrap_err(#error_message)?); // TODO: possible optimization: buffer size
+            let mut #index_identifier = RecordingIndex::new(std::io::BufWriter::new(std::fs::File::create(format!(#index_file_path)).wrap_err(#index_error_message)?));
         }
     });
-    let frame_writes = cyclers.instances().map(|(_cycler, instance)| {
+    let frame_writes = cyclers.instances().map(|(cycler, instance)| {
         let instance_name = format_ident!("{}", instance);
+        let encoder = recording_encoder(cycler);
         let instance_name_snake_case = format_ident!("{}", instance.to_case(Case::Snake));
+        let index_identifier = format_ident!("{}_index", instance.to_case(Case::Snake));
         let error_message = format!("failed to write into recording file for {instance}");
+        let index_error_message = format!("failed to write into recording index file for {instance}");
         quote! {
-            crate::cyclers::RecordingFrame::#instance_name { data } => #instance_name_snake_case.write_all(data.as_slice()).wrap_err(#error_message)?,
+            crate::cyclers::RecordingFrame::#instance_name { kind, timestamp, data } => {
+                let mut kind_data = Vec::new();
+                <#encoder as crate::cyclers::FrameEncoder>::serialize_value(&mut kind_data, &kind).wrap_err(#error_message)?;
+                #index_identifier.record_frame::<#encoder>(timestamp, (kind_data.len() + data.len()) as u64).wrap_err(#index_error_message)?;
+                #instance_name_snake_case.write_all(kind_data.as_slice()).wrap_err(#error_message)?;
+                #instance_name_snake_case.write_all(data.as_slice()).wrap_err(#error_message)?;
+            },
         }
     });
 
@@ -142,6 +158,36 @@ fn generate_recording_thread(cyclers: &Cyclers) -> TokenStream {
                 .spawn(move || -> color_eyre::Result<()> {
                     let result = (|| {
                         use std::io::Write;
+
+                        /// Every n-th frame of a recording gets an entry (timestamp, byte offset)
+                        /// in the accompanying index file to allow seeking without reading linearly
+                        const FRAMES_PER_INDEX_ENTRY: usize = 100;
+
+                        struct RecordingIndex<Writer> {
+                            writer: Writer,
+                            number_of_frames: usize,
+                            byte_offset: u64,
+                        }
+
+                        impl<Writer: std::io::Write> RecordingIndex<Writer> {
+                            fn new(writer: Writer) -> Self {
+                                Self {
+                                    writer,
+                                    number_of_frames: 0,
+                                    byte_offset: 0,
+                                }
+                            }
+
+                            fn record_frame<Encoder: crate::cyclers::FrameEncoder>(&mut self, timestamp: std::time::SystemTime, frame_length: u64) -> color_eyre::Result<()> {
+                                if self.number_of_frames % FRAMES_PER_INDEX_ENTRY == 0 {
+                                    Encoder::serialize_value(&mut self.writer, &(timestamp, self.byte_offset))?;
+                                }
+                                self.number_of_frames += 1;
+                                self.byte_offset += frame_length;
+                                Ok(())
+                            }
+                        }
+
                         let seconds = std::time::SystemTime::now().duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs();
                         #(#file_creations)*
                         for recording_frame in recording_receiver {
@@ -213,6 +259,9 @@ fn generate_cycler_constructors(cyclers: &Cyclers) -> TokenStream {
                 #(#other_cycler_inputs,)*
                 recording_sender.clone(),
                 enable_recording,
+                enable_instrumentation,
+                &body_id,
+                &head_id,
             )
             .wrap_err(#error_message)?;
             communication_server.register_cycler_instance(
@@ -266,3 +315,8 @@ fn generate_cycler_joins(cyclers: &Cyclers) -> TokenStream {
         })
         .collect()
 }
+
+fn recording_encoder(cycler: &Cycler) -> TokenStream {
+    let module_name = format_ident!("{}", cycler.name.to_case(Case::Snake));
+    quote! { crate::cyclers::#module_name::RecordingEncoder }
+}
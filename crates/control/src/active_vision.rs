@@ -126,7 +126,12 @@ fn closest_interesting_obstacle_visible(
 ) -> Option<Point2<f32>> {
     obstacles
         .iter()
-        .filter(|obstacle| matches!(obstacle.kind, ObstacleKind::Robot | ObstacleKind::Unknown))
+        .filter(|obstacle| {
+            matches!(
+                obstacle.kind,
+                ObstacleKind::Robot | ObstacleKind::Teammate | ObstacleKind::Unknown
+            )
+        })
         .map(|obstacle| obstacle.position)
         .filter(|obstacle_position| is_position_visible(*obstacle_position, parameters))
         .min_by_key(|position| NotNan::new(position.coords.norm()).unwrap())
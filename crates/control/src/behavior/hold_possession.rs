@@ -0,0 +1,115 @@
+use framework::AdditionalOutput;
+use geometry::look_at::LookAt;
+use nalgebra::{point, Isometry2, Point2};
+use spl_network_messages::{GamePhase, Half};
+use types::{
+    field_dimensions::FieldDimensions,
+    filtered_game_controller_state::FilteredGameControllerState,
+    motion_command::MotionCommand,
+    obstacles::{Obstacle, ObstacleKind},
+    parameters::HoldPossessionParameters,
+    path_obstacles::PathObstacle,
+    world_state::WorldState,
+};
+
+use super::{head::LookAction, walk_to_pose::WalkAndStand};
+
+pub fn execute(
+    world_state: &WorldState,
+    walk_and_stand: &WalkAndStand,
+    look_action: &LookAction,
+    field_dimensions: &FieldDimensions,
+    parameters: &HoldPossessionParameters,
+    path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
+) -> Option<MotionCommand> {
+    if !parameters.enabled {
+        return None;
+    }
+    let FilteredGameControllerState {
+        game_phase,
+        half,
+        remaining_time_in_half,
+        own_score,
+        opponent_score,
+        ..
+    } = world_state.filtered_game_controller_state?;
+    let is_final_minutes_of_regular_play = matches!(game_phase, GamePhase::Normal)
+        && half == Half::Second
+        && remaining_time_in_half <= parameters.remaining_time_threshold;
+    let is_leading = own_score >= opponent_score.saturating_add(parameters.minimum_goal_lead);
+    if !is_final_minutes_of_regular_play || !is_leading {
+        return None;
+    }
+
+    let ball = world_state.ball?.ball_in_ground;
+    let robot_to_field = world_state.robot.robot_to_field?;
+    let own_goal = robot_to_field.inverse() * point![-field_dimensions.length / 2.0, 0.0];
+    let holding_pose = holding_pose(
+        &world_state.obstacles,
+        ball,
+        own_goal,
+        parameters.holding_distance,
+    )?;
+    walk_and_stand.execute(holding_pose, look_action.execute(), path_obstacles_output)
+}
+
+fn holding_pose(
+    obstacles: &[Obstacle],
+    ball: Point2<f32>,
+    own_goal: Point2<f32>,
+    holding_distance: f32,
+) -> Option<Isometry2<f32>> {
+    // teammates are reported as ObstacleKind::Teammate and must not be covered
+    let closest_opponent = obstacles
+        .iter()
+        .filter(|obstacle| matches!(obstacle.kind, ObstacleKind::Robot))
+        .map(|obstacle| obstacle.position)
+        .min_by(|left, right| (left - ball).norm().total_cmp(&(right - ball).norm()));
+    let covered_position = closest_opponent.unwrap_or(own_goal);
+
+    let holding_position =
+        ball + (covered_position - ball).try_normalize(f32::EPSILON)? * holding_distance;
+    Some(Isometry2::new(
+        holding_position.coords,
+        holding_position.look_at(&ball).angle(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn teammate(position: Point2<f32>) -> Obstacle {
+        Obstacle {
+            kind: ObstacleKind::Teammate,
+            ..Obstacle::robot(position, 0.3, 0.3)
+        }
+    }
+
+    #[test]
+    fn teammates_are_not_covered() {
+        let ball = point![1.0, 0.0];
+        let own_goal = point![-4.5, 0.0];
+        let obstacles = [
+            teammate(point![1.5, 0.0]),
+            Obstacle::robot(point![1.0, 2.0], 0.3, 0.3),
+        ];
+
+        let holding_pose = holding_pose(&obstacles, ball, own_goal, 0.5).unwrap();
+
+        assert_relative_eq!(holding_pose.translation.vector, point![1.0, 0.5].coords);
+    }
+
+    #[test]
+    fn own_goal_is_covered_if_only_teammates_are_around() {
+        let ball = point![1.0, 0.0];
+        let own_goal = point![-4.5, 0.0];
+        let obstacles = [teammate(point![1.5, 0.0])];
+
+        let holding_pose = holding_pose(&obstacles, ball, own_goal, 0.5).unwrap();
+
+        assert_relative_eq!(holding_pose.translation.vector, point![0.5, 0.0].coords);
+    }
+}
@@ -0,0 +1,70 @@
+use framework::AdditionalOutput;
+use geometry::look_at::LookAt;
+use nalgebra::{point, Isometry2};
+use types::{
+    field_dimensions::FieldDimensions, motion_command::MotionCommand, obstacles::ObstacleKind,
+    parameters::MarkOpponentParameters, path_obstacles::PathObstacle, support_foot::Side,
+    world_state::WorldState,
+};
+
+use super::{head::LookAction, walk_to_pose::WalkAndStand};
+
+pub fn opponent_to_mark(
+    world_state: &WorldState,
+    field_dimensions: &FieldDimensions,
+    parameters: &MarkOpponentParameters,
+    side: Side,
+) -> Option<u8> {
+    if !parameters.enabled {
+        return None;
+    }
+    let robot_to_field = world_state.robot.robot_to_field?;
+    let own_goal = point![-field_dimensions.length / 2.0, 0.0];
+    let (index, _) = world_state
+        .obstacles
+        .iter()
+        .enumerate()
+        .filter(|(_, obstacle)| matches!(obstacle.kind, ObstacleKind::Robot))
+        .map(|(index, obstacle)| (index, robot_to_field * obstacle.position))
+        .filter(|(_, position)| {
+            let is_on_side = match side {
+                Side::Left => position.y >= 0.0,
+                Side::Right => position.y <= 0.0,
+            };
+            is_on_side
+                && position.x < 0.0
+                && (position - own_goal).norm() < parameters.maximum_distance_to_own_goal
+        })
+        .min_by(|(_, left), (_, right)| {
+            (left - own_goal)
+                .norm()
+                .total_cmp(&(right - own_goal).norm())
+        })?;
+    index.try_into().ok()
+}
+
+pub fn execute(
+    world_state: &WorldState,
+    obstacle_index: u8,
+    walk_and_stand: &WalkAndStand,
+    look_action: &LookAction,
+    field_dimensions: &FieldDimensions,
+    parameters: &MarkOpponentParameters,
+    path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
+) -> Option<MotionCommand> {
+    let opponent = world_state.obstacles.get(obstacle_index as usize)?.position;
+    let robot_to_field = world_state.robot.robot_to_field?;
+    let own_goal = robot_to_field.inverse() * point![-field_dimensions.length / 2.0, 0.0];
+
+    let marking_position =
+        opponent + (own_goal - opponent).try_normalize(f32::EPSILON)? * parameters.marking_distance;
+    let look_target = world_state
+        .ball
+        .map(|ball| ball.ball_in_ground)
+        .unwrap_or(opponent);
+    let marking_pose = Isometry2::new(
+        marking_position.coords,
+        marking_position.look_at(&look_target).angle(),
+    );
+    walk_and_stand.execute(marking_pose, look_action.execute(), path_obstacles_output)
+}
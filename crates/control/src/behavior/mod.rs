@@ -3,19 +3,24 @@ mod defend;
 mod dribble;
 mod fall_safely;
 mod head;
+mod hold_possession;
 mod initial;
 mod intercept_ball;
 mod jump;
 mod look_around;
 mod lost_ball;
+mod mark_opponent;
 pub mod node;
 mod penalize;
+mod penalty_shootout_hold;
 mod prepare_jump;
 mod search;
+mod shield_ball;
 mod sit_down;
 mod stand;
 mod stand_up;
 mod support;
+mod support_free_kick;
 mod unstiff;
 mod walk_to_kick_off;
 mod walk_to_penalty_kick;
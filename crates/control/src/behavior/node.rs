@@ -3,7 +3,7 @@ use std::time::SystemTime;
 use color_eyre::Result;
 use context_attribute::context;
 use framework::{AdditionalOutput, MainOutput};
-use nalgebra::{point, Point2, Vector2};
+use nalgebra::{point, Isometry2, Point2, Vector2};
 use serde::{Deserialize, Serialize};
 use spl_network_messages::{GamePhase, SubState, Team};
 use types::{
@@ -21,6 +21,7 @@ use types::{
     primary_state::PrimaryState,
     roles::Role,
     step_plan::Step,
+    support_decision::SupportDecision,
     support_foot::Side,
     world_state::WorldState,
 };
@@ -32,8 +33,10 @@ use super::{
     defend::Defend,
     dribble, fall_safely,
     head::LookAction,
-    initial, intercept_ball, jump, look_around, lost_ball, penalize, prepare_jump, search,
-    sit_down, stand, stand_up, support, unstiff, walk_to_kick_off, walk_to_penalty_kick,
+    hold_possession, initial, intercept_ball, jump, look_around, lost_ball, mark_opponent,
+    penalize, penalty_shootout_hold, prepare_jump, search, shield_ball, sit_down, stand, stand_up,
+    support::{self, RuleBallFade},
+    support_free_kick, unstiff, walk_to_kick_off, walk_to_penalty_kick,
     walk_to_pose::{WalkAndStand, WalkPathPlanner},
 };
 
@@ -42,6 +45,8 @@ pub struct Behavior {
     last_motion_command: MotionCommand,
     absolute_last_known_ball_position: Point2<f32>,
     active_since: Option<SystemTime>,
+    last_support_pose: Option<Isometry2<f32>>,
+    rule_ball_fade: RuleBallFade,
 }
 
 #[context]
@@ -52,6 +57,7 @@ pub struct CycleContext {
     path_obstacles: AdditionalOutput<Vec<PathObstacle>, "path_obstacles">,
     dribble_path_obstacles: AdditionalOutput<Vec<PathObstacle>, "dribble_path_obstacles">,
     active_action: AdditionalOutput<Action, "active_action">,
+    support_decision: AdditionalOutput<SupportDecision, "support_decision">,
 
     has_ground_contact: Input<bool, "has_ground_contact">,
     world_state: Input<WorldState, "world_state">,
@@ -79,6 +85,8 @@ impl Behavior {
             last_motion_command: MotionCommand::Unstiff,
             absolute_last_known_ball_position: point![0.0, 0.0],
             active_since: None,
+            last_support_pose: None,
+            rule_ball_fade: Default::default(),
         })
     }
 
@@ -96,6 +104,12 @@ impl Behavior {
         }
 
         let now = context.cycle_time.start_time;
+        self.rule_ball_fade.update(
+            world_state.rule_ball,
+            world_state.ball,
+            now,
+            &context.parameters.support,
+        );
         match (self.active_since, world_state.robot.primary_state) {
             (
                 None,
@@ -116,6 +130,7 @@ impl Behavior {
             Action::Initial,
             Action::FallSafely,
             Action::StandUp,
+            Action::PenaltyShootoutHold,
             Action::Stand,
             Action::InterceptBall,
             Action::Calibrate,
@@ -132,8 +147,28 @@ impl Behavior {
             .map(|filtered_game_controller_state| filtered_game_controller_state.game_state);
 
         match world_state.robot.role {
-            Role::DefenderLeft => actions.push(Action::DefendLeft),
-            Role::DefenderRight => actions.push(Action::DefendRight),
+            Role::DefenderLeft => {
+                if let Some(obstacle_index) = mark_opponent::opponent_to_mark(
+                    world_state,
+                    context.field_dimensions,
+                    &context.parameters.mark_opponent,
+                    Side::Left,
+                ) {
+                    actions.push(Action::MarkOpponent { obstacle_index });
+                }
+                actions.push(Action::DefendLeft);
+            }
+            Role::DefenderRight => {
+                if let Some(obstacle_index) = mark_opponent::opponent_to_mark(
+                    world_state,
+                    context.field_dimensions,
+                    &context.parameters.mark_opponent,
+                    Side::Right,
+                ) {
+                    actions.push(Action::MarkOpponent { obstacle_index });
+                }
+                actions.push(Action::DefendRight);
+            }
             Role::Keeper => match world_state.filtered_game_controller_state {
                 Some(FilteredGameControllerState {
                     game_phase: GamePhase::PenaltyShootout { .. },
@@ -145,8 +180,14 @@ impl Behavior {
                 _ => actions.push(Action::DefendGoal),
             },
             Role::Loser => actions.push(Action::SearchForLostBall),
-            Role::MidfielderLeft => actions.push(Action::SupportLeft),
-            Role::MidfielderRight => actions.push(Action::SupportRight),
+            Role::MidfielderLeft => {
+                actions.push(Action::SupportFreeKick);
+                actions.push(Action::SupportLeft);
+            }
+            Role::MidfielderRight => {
+                actions.push(Action::SupportFreeKick);
+                actions.push(Action::SupportRight);
+            }
             Role::ReplacementKeeper => actions.push(Action::DefendGoal),
             Role::Searcher => actions.push(Action::Search),
             Role::Striker => match filtered_game_state {
@@ -154,6 +195,8 @@ impl Behavior {
                 | Some(FilteredGameState::Playing {
                     ball_is_free: true, ..
                 }) => {
+                    actions.push(Action::HoldPossession);
+                    actions.push(Action::ShieldBall);
                     actions.push(Action::Dribble);
                 }
                 Some(FilteredGameState::Ready {
@@ -175,7 +218,10 @@ impl Behavior {
                     _ => actions.push(Action::DefendKickOff),
                 },
             },
-            Role::StrikerSupporter => actions.push(Action::SupportStriker),
+            Role::StrikerSupporter => {
+                actions.push(Action::SupportFreeKick);
+                actions.push(Action::SupportStriker);
+            }
         };
 
         let walk_path_planner = WalkPathPlanner::new(
@@ -234,12 +280,39 @@ impl Behavior {
                         *context.intercept_ball_parameters,
                         *context.maximum_step_size,
                     ),
+                    Action::HoldPossession => hold_possession::execute(
+                        world_state,
+                        &walk_and_stand,
+                        &look_action,
+                        context.field_dimensions,
+                        &context.parameters.hold_possession,
+                        &mut context.path_obstacles,
+                    ),
+                    Action::ShieldBall => shield_ball::execute(
+                        world_state,
+                        &walk_and_stand,
+                        &look_action,
+                        &context.parameters.shield_ball,
+                        &mut context.path_obstacles,
+                    ),
                     Action::Calibrate => calibrate::execute(world_state),
                     Action::DefendGoal => defend.goal(&mut context.path_obstacles),
                     Action::DefendKickOff => defend.kick_off(&mut context.path_obstacles),
                     Action::DefendLeft => defend.left(&mut context.path_obstacles),
                     Action::DefendRight => defend.right(&mut context.path_obstacles),
                     Action::DefendPenaltyKick => defend.penalty_kick(&mut context.path_obstacles),
+                    Action::MarkOpponent { obstacle_index } => mark_opponent::execute(
+                        world_state,
+                        *obstacle_index,
+                        &walk_and_stand,
+                        &look_action,
+                        context.field_dimensions,
+                        &context.parameters.mark_opponent,
+                        &mut context.path_obstacles,
+                    ),
+                    Action::PenaltyShootoutHold => {
+                        penalty_shootout_hold::execute(world_state, context.field_dimensions)
+                    }
                     Action::Stand => stand::execute(world_state, context.field_dimensions),
                     Action::Dribble => dribble::execute(
                         world_state,
@@ -278,9 +351,13 @@ impl Behavior {
                             .role_positions
                             .left_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
                         context.parameters.role_positions.left_midfielder_minimum_x,
+                        &context.parameters.support,
                         &walk_and_stand,
                         &look_action,
                         &mut context.path_obstacles,
+                        &mut context.support_decision,
+                        &mut self.last_support_pose,
+                        &self.rule_ball_fade,
                     ),
                     Action::SupportRight => support::execute(
                         world_state,
@@ -295,9 +372,13 @@ impl Behavior {
                             .role_positions
                             .right_midfielder_maximum_x_in_ready_and_when_ball_is_not_free,
                         context.parameters.role_positions.right_midfielder_minimum_x,
+                        &context.parameters.support,
                         &walk_and_stand,
                         &look_action,
                         &mut context.path_obstacles,
+                        &mut context.support_decision,
+                        &mut self.last_support_pose,
+                        &self.rule_ball_fade,
                     ),
                     Action::SupportStriker => support::execute(
                         world_state,
@@ -315,6 +396,18 @@ impl Behavior {
                             .parameters
                             .role_positions
                             .striker_supporter_minimum_x,
+                        &context.parameters.support,
+                        &walk_and_stand,
+                        &look_action,
+                        &mut context.path_obstacles,
+                        &mut context.support_decision,
+                        &mut self.last_support_pose,
+                        &self.rule_ball_fade,
+                    ),
+                    Action::SupportFreeKick => support_free_kick::execute(
+                        world_state,
+                        context.field_dimensions,
+                        &context.parameters.support_free_kick,
                         &walk_and_stand,
                         &look_action,
                         &mut context.path_obstacles,
@@ -344,6 +437,12 @@ impl Behavior {
         context.active_action.fill_if_subscribed(|| *action);
 
         self.last_motion_command = motion_command.clone();
+        if !matches!(
+            action,
+            Action::SupportLeft | Action::SupportRight | Action::SupportStriker
+        ) {
+            self.last_support_pose = None;
+        }
 
         if matches!(action, Action::Dribble) {
             context
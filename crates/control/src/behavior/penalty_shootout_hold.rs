@@ -0,0 +1,26 @@
+use spl_network_messages::GamePhase;
+use types::{
+    field_dimensions::FieldDimensions, filtered_game_controller_state::FilteredGameControllerState,
+    motion_command::MotionCommand, primary_state::PrimaryState, world_state::WorldState,
+};
+
+use super::stand;
+
+pub fn execute(
+    world_state: &WorldState,
+    field_dimensions: &FieldDimensions,
+) -> Option<MotionCommand> {
+    match (
+        world_state.robot.primary_state,
+        world_state.filtered_game_controller_state,
+    ) {
+        (
+            PrimaryState::Set,
+            Some(FilteredGameControllerState {
+                game_phase: GamePhase::PenaltyShootout { .. },
+                ..
+            }),
+        ) => stand::execute(world_state, field_dimensions),
+        _ => None,
+    }
+}
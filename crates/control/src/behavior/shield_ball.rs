@@ -0,0 +1,37 @@
+use framework::AdditionalOutput;
+use geometry::look_at::LookAt;
+use nalgebra::Isometry2;
+use types::{
+    motion_command::MotionCommand, obstacles::ObstacleKind, parameters::ShieldBallParameters,
+    path_obstacles::PathObstacle, world_state::WorldState,
+};
+
+use super::{head::LookAction, walk_to_pose::WalkAndStand};
+
+pub fn execute(
+    world_state: &WorldState,
+    walk_and_stand: &WalkAndStand,
+    look_action: &LookAction,
+    parameters: &ShieldBallParameters,
+    path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
+) -> Option<MotionCommand> {
+    if !parameters.enabled {
+        return None;
+    }
+    let ball = world_state.ball?.ball_in_ground;
+    let closest_opponent = world_state
+        .obstacles
+        .iter()
+        .filter(|obstacle| matches!(obstacle.kind, ObstacleKind::Robot))
+        .map(|obstacle| obstacle.position)
+        .filter(|position| (position - ball).norm() < parameters.pressure_distance)
+        .min_by(|left, right| (left - ball).norm().total_cmp(&(right - ball).norm()))?;
+
+    let shielding_position = ball
+        + (closest_opponent - ball).try_normalize(f32::EPSILON)? * parameters.shielding_distance;
+    let shielding_pose = Isometry2::new(
+        shielding_position.coords,
+        shielding_position.look_at(&ball).angle(),
+    );
+    walk_and_stand.execute(shielding_pose, look_action.execute(), path_obstacles_output)
+}
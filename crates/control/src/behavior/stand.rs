@@ -20,11 +20,18 @@ pub fn execute(
         PrimaryState::Set => {
             let robot_to_field = world_state.robot.robot_to_field?;
             let fallback_target = match world_state.filtered_game_controller_state {
-                Some(FilteredGameControllerState {
-                    sub_state: Some(SubState::PenaltyKick),
-                    kicking_team,
-                    ..
-                }) => {
+                Some(
+                    FilteredGameControllerState {
+                        game_phase: GamePhase::PenaltyShootout { .. },
+                        kicking_team,
+                        ..
+                    }
+                    | FilteredGameControllerState {
+                        sub_state: Some(SubState::PenaltyKick),
+                        kicking_team,
+                        ..
+                    },
+                ) => {
                     let side_factor = match kicking_team {
                         Team::Opponent => -1.0,
                         _ => 1.0,
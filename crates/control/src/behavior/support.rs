@@ -1,19 +1,83 @@
-use std::f32::consts::FRAC_PI_4;
+use std::{
+    f32::consts::FRAC_PI_4,
+    time::{Duration, SystemTime},
+};
 
 use framework::AdditionalOutput;
 use geometry::look_at::LookAt;
-use nalgebra::{point, Isometry2, UnitComplex, Vector2};
+use nalgebra::{point, Isometry2, Point2, UnitComplex, Vector2};
+use serde::{Deserialize, Serialize};
 use types::{
     field_dimensions::FieldDimensions,
     filtered_game_state::FilteredGameState,
-    motion_command::MotionCommand,
+    motion_command::{HeadMotion, MotionCommand},
+    parameters::{SupportFallbackParameters, SupportParameters, SupportSideParameters},
     path_obstacles::PathObstacle,
+    support_decision::{SupportBallSource, SupportDecision},
     support_foot::Side,
     world_state::{BallState, WorldState},
 };
 
 use super::{head::LookAction, walk_to_pose::WalkAndStand};
 
+/// Fades the rule ball in and out around set play transitions instead of switching abruptly
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct RuleBallFade {
+    rule_ball_weight: f32,
+    last_rule_ball: Option<BallState>,
+    last_update: Option<SystemTime>,
+}
+
+impl RuleBallFade {
+    pub fn update(
+        &mut self,
+        rule_ball: Option<BallState>,
+        ball: Option<BallState>,
+        now: SystemTime,
+        parameters: &SupportParameters,
+    ) {
+        let target_weight = match rule_ball {
+            Some(_) => {
+                let configured_weight = parameters.rule_ball_weight.clamp(0.0, 1.0);
+                let ball_staleness = ball_staleness(ball, now, parameters.stale_ball_age);
+                configured_weight + (1.0 - configured_weight) * ball_staleness
+            }
+            None => 0.0,
+        };
+        self.rule_ball_weight = if parameters.rule_ball_fade_duration.is_zero() {
+            target_weight
+        } else {
+            let elapsed = self
+                .last_update
+                .and_then(|last_update| now.duration_since(last_update).ok())
+                .unwrap_or_default();
+            let maximum_step =
+                elapsed.as_secs_f32() / parameters.rule_ball_fade_duration.as_secs_f32();
+            self.rule_ball_weight
+                + (target_weight - self.rule_ball_weight).clamp(-maximum_step, maximum_step)
+        };
+        if rule_ball.is_some() {
+            self.last_rule_ball = rule_ball;
+        } else if self.rule_ball_weight <= 0.0 {
+            self.last_rule_ball = None;
+        }
+        self.last_update = Some(now);
+    }
+}
+
+/// Balls that have not been seen for `stale_ball_age` are fully replaced by the rule ball, a zero
+/// age keeps the configured weight regardless of when the ball was seen
+fn ball_staleness(ball: Option<BallState>, now: SystemTime, stale_ball_age: Duration) -> f32 {
+    let Some(ball) = ball else {
+        return 1.0;
+    };
+    if stale_ball_age.is_zero() {
+        return 0.0;
+    }
+    let ball_age = now.duration_since(ball.last_seen_ball).unwrap_or_default();
+    (ball_age.as_secs_f32() / stale_ball_age.as_secs_f32()).clamp(0.0, 1.0)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn execute(
     world_state: &WorldState,
@@ -22,64 +86,330 @@ pub fn execute(
     distance_to_ball: f32,
     maximum_x_in_ready_and_when_ball_is_not_free: f32,
     minimum_x: f32,
+    parameters: &SupportParameters,
     walk_and_stand: &WalkAndStand,
     look_action: &LookAction,
     path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
+    support_decision_output: &mut AdditionalOutput<SupportDecision>,
+    last_support_pose: &mut Option<Isometry2<f32>>,
+    rule_ball_fade: &RuleBallFade,
 ) -> Option<MotionCommand> {
-    let pose = support_pose(
+    let Some((support_pose, decision)) = support_pose(
         world_state,
+        rule_ball_fade,
         field_dimensions,
         field_side,
         distance_to_ball,
         maximum_x_in_ready_and_when_ball_is_not_free,
         minimum_x,
-    )?;
-    walk_and_stand.execute(pose, look_action.execute(), path_obstacles_output)
+        parameters,
+    ) else {
+        return match parameters.fallback {
+            SupportFallbackParameters::Disabled => None,
+            SupportFallbackParameters::StandAndLookAround => Some(MotionCommand::Stand {
+                head: HeadMotion::LookAround,
+            }),
+        };
+    };
+    support_decision_output.fill_if_subscribed(|| decision);
+    let support_pose = match *last_support_pose {
+        Some(last_support_pose) => smooth_pose(
+            last_support_pose,
+            support_pose,
+            parameters.target_smoothing_factor,
+        ),
+        None => support_pose,
+    };
+    *last_support_pose = Some(support_pose);
+    let robot_to_field = world_state.robot.robot_to_field?;
+    walk_and_stand.execute(
+        robot_to_field.inverse() * support_pose,
+        look_action.execute(),
+        path_obstacles_output,
+    )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn support_pose(
     world_state: &WorldState,
+    rule_ball_fade: &RuleBallFade,
     field_dimensions: &FieldDimensions,
     field_side: Option<Side>,
     distance_to_ball: f32,
     maximum_x_in_ready_and_when_ball_is_not_free: f32,
     minimum_x: f32,
-) -> Option<Isometry2<f32>> {
+    parameters: &SupportParameters,
+) -> Option<(Isometry2<f32>, SupportDecision)> {
     let robot_to_field = world_state.robot.robot_to_field?;
-    let ball = world_state
-        .rule_ball
-        .or(world_state.ball)
-        .unwrap_or_else(|| BallState::new_at_center(robot_to_field));
-    let side = field_side.unwrap_or_else(|| ball.field_side.opposite());
-    let offset_vector = UnitComplex::new(match side {
+    let (ball, ball_source) = support_ball(rule_ball_fade, world_state.ball, robot_to_field);
+    let side =
+        field_side
+            .or(parameters.forced_side)
+            .unwrap_or(match parameters.side_relative_to_ball {
+                SupportSideParameters::OppositeOfBall => ball.field_side.opposite(),
+                SupportSideParameters::SameAsBall => ball.field_side,
+            });
+    let offset_angle = match side {
         Side::Left => -FRAC_PI_4,
         Side::Right => FRAC_PI_4,
-    }) * -(Vector2::x() * distance_to_ball);
+    };
+    let offset_vector = UnitComplex::new(offset_angle) * -(Vector2::x() * distance_to_ball);
     let supporting_position = ball.ball_in_field + offset_vector;
 
     let filtered_game_state = world_state
         .filtered_game_controller_state
         .map(|filtered_game_controller_state| filtered_game_controller_state.game_state);
-    let clamped_x = match filtered_game_state {
+    let is_attacking = matches!(
+        filtered_game_state,
+        Some(FilteredGameState::Playing {
+            ball_is_free: true,
+            ..
+        })
+    ) && ball.ball_in_field.x > 0.0;
+    let is_goalkeeper =
+        world_state
+            .filtered_game_controller_state
+            .is_some_and(|filtered_game_controller_state| {
+                filtered_game_controller_state.goal_keeper_player_number
+                    == world_state.robot.player_number
+            });
+    let supporting_position = if is_attacking && !is_goalkeeper {
+        avoid_opponent_penalty_area(
+            supporting_position,
+            field_dimensions,
+            parameters.penalty_area_avoidance,
+        )
+    } else {
+        supporting_position
+    };
+    let (clamp_minimum_x, clamp_maximum_x) = match filtered_game_state {
         Some(FilteredGameState::Ready { .. })
         | Some(FilteredGameState::Playing {
             ball_is_free: false,
             ..
-        }) => supporting_position.x.clamp(
+        }) => (
             minimum_x.min(maximum_x_in_ready_and_when_ball_is_not_free),
             minimum_x.max(maximum_x_in_ready_and_when_ball_is_not_free),
         ),
-        _ => supporting_position
-            .x
-            .clamp(minimum_x, field_dimensions.length / 2.0),
+        _ => (minimum_x, field_dimensions.length / 2.0),
     };
+    let (clamp_minimum_y, clamp_maximum_y) =
+        (-field_dimensions.width / 2.0, field_dimensions.width / 2.0);
+    let clamped_x = supporting_position
+        .x
+        .clamp(clamp_minimum_x, clamp_maximum_x);
     let clamped_y = supporting_position
         .y
-        .clamp(-field_dimensions.width / 2.0, field_dimensions.width / 2.0);
+        .clamp(clamp_minimum_y, clamp_maximum_y);
     let clamped_position = point![clamped_x, clamped_y];
     let support_pose = Isometry2::new(
         clamped_position.coords,
         clamped_position.look_at(&ball.ball_in_field).angle(),
     );
-    Some(robot_to_field.inverse() * support_pose)
+    let decision = SupportDecision {
+        side,
+        ball_source,
+        offset_angle,
+        minimum_x: clamp_minimum_x,
+        maximum_x: clamp_maximum_x,
+        minimum_y: clamp_minimum_y,
+        maximum_y: clamp_maximum_y,
+    };
+    Some((support_pose, decision))
+}
+
+fn support_ball(
+    rule_ball_fade: &RuleBallFade,
+    ball: Option<BallState>,
+    robot_to_field: Isometry2<f32>,
+) -> (BallState, SupportBallSource) {
+    match (rule_ball_fade.last_rule_ball, ball) {
+        (Some(rule_ball), Some(_)) if rule_ball_fade.rule_ball_weight >= 1.0 => {
+            (rule_ball, SupportBallSource::RuleBall)
+        }
+        (Some(rule_ball), Some(ball)) if rule_ball_fade.rule_ball_weight > 0.0 => (
+            blend_balls(rule_ball, ball, rule_ball_fade.rule_ball_weight),
+            SupportBallSource::Blended,
+        ),
+        (Some(rule_ball), None) => (rule_ball, SupportBallSource::RuleBall),
+        (_, Some(ball)) => (ball, SupportBallSource::Ball),
+        (None, None) => (
+            BallState::new_at_center(robot_to_field),
+            SupportBallSource::FieldCenter,
+        ),
+    }
+}
+
+fn blend_balls(rule_ball: BallState, ball: BallState, rule_ball_weight: f32) -> BallState {
+    BallState {
+        ball_in_field: ball.ball_in_field
+            + (rule_ball.ball_in_field - ball.ball_in_field) * rule_ball_weight,
+        ball_in_ground: ball.ball_in_ground
+            + (rule_ball.ball_in_ground - ball.ball_in_ground) * rule_ball_weight,
+        field_side: if rule_ball_weight >= 0.5 {
+            rule_ball.field_side
+        } else {
+            ball.field_side
+        },
+        ..rule_ball
+    }
+}
+
+/// Smoothing factors of 1 would keep the last pose forever, larger ones move away from the target
+const MAXIMUM_SMOOTHING_FACTOR: f32 = 0.99;
+
+fn smooth_pose(
+    last_pose: Isometry2<f32>,
+    target_pose: Isometry2<f32>,
+    smoothing_factor: f32,
+) -> Isometry2<f32> {
+    let interpolation_factor = 1.0 - smoothing_factor.clamp(0.0, MAXIMUM_SMOOTHING_FACTOR);
+    Isometry2::from_parts(
+        last_pose
+            .translation
+            .vector
+            .lerp(&target_pose.translation.vector, interpolation_factor)
+            .into(),
+        last_pose
+            .rotation
+            .slerp(&target_pose.rotation, interpolation_factor),
+    )
+}
+
+fn avoid_opponent_penalty_area(
+    position: Point2<f32>,
+    field_dimensions: &FieldDimensions,
+    avoidance: f32,
+) -> Point2<f32> {
+    let penalty_area_front_x = field_dimensions.length / 2.0 - field_dimensions.penalty_area_length;
+    let penalty_area_half_width = field_dimensions.penalty_area_width / 2.0;
+    if position.x <= penalty_area_front_x || position.y.abs() >= penalty_area_half_width {
+        return position;
+    }
+
+    let distance_to_front = position.x - penalty_area_front_x;
+    let distance_to_side = penalty_area_half_width - position.y.abs();
+    let closest_position_outside = if distance_to_front < distance_to_side {
+        point![penalty_area_front_x, position.y]
+    } else {
+        point![position.x, penalty_area_half_width.copysign(position.y)]
+    };
+    position + (closest_position_outside - position) * avoidance.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use approx::assert_relative_eq;
+    use nalgebra::vector;
+
+    use super::*;
+
+    fn ball_at(position: Point2<f32>, last_seen_ball: SystemTime) -> BallState {
+        BallState {
+            ball_in_field: position,
+            last_seen_ball,
+            ..BallState::new_at_center(Isometry2::identity())
+        }
+    }
+
+    #[test]
+    fn support_ball_stays_continuous_around_set_play_transitions() {
+        let parameters = SupportParameters {
+            rule_ball_weight: 0.5,
+            rule_ball_fade_duration: Duration::from_secs(1),
+            stale_ball_age: Duration::from_secs(2),
+            ..Default::default()
+        };
+        let cycle_duration = Duration::from_millis(12);
+        let rule_ball_position = point![3.0, 0.0];
+        let ball_position = point![-2.0, 1.0];
+        // the weight changes by at most one per fade duration
+        let maximum_step = (rule_ball_position - ball_position).norm()
+            * cycle_duration.as_secs_f32()
+            / parameters.rule_ball_fade_duration.as_secs_f32();
+        let start = UNIX_EPOCH + Duration::from_secs(100);
+
+        let mut rule_ball_fade = RuleBallFade::default();
+        let mut last_position = None;
+        let mut sources = Vec::new();
+        for cycle in 0..800 {
+            let now = start + cycle_duration * cycle;
+            let rule_ball = (100..600)
+                .contains(&cycle)
+                .then(|| ball_at(rule_ball_position, now));
+            // the ball is not seen anymore during the set play and lost after a while
+            let ball = match cycle {
+                200..=399 => Some(ball_at(ball_position, start + cycle_duration * 200)),
+                400..=449 => None,
+                _ => Some(ball_at(ball_position, now)),
+            };
+            rule_ball_fade.update(rule_ball, ball, now, &parameters);
+
+            let (ball, source) = support_ball(&rule_ball_fade, ball, Isometry2::identity());
+
+            if let Some(last_position) = last_position {
+                let step: Vector2<f32> = ball.ball_in_field - last_position;
+                assert!(
+                    step.norm() <= maximum_step + 1e-4,
+                    "support ball jumped by {} in cycle {cycle}",
+                    step.norm(),
+                );
+            }
+            last_position = Some(ball.ball_in_field);
+            sources.push(source);
+        }
+
+        assert!(matches!(sources[99], SupportBallSource::Ball));
+        assert!(matches!(sources[150], SupportBallSource::Blended));
+        assert!(matches!(sources[420], SupportBallSource::RuleBall));
+        assert!(matches!(sources[550], SupportBallSource::Blended));
+        assert!(matches!(sources[799], SupportBallSource::Ball));
+    }
+
+    #[test]
+    fn zero_fade_duration_switches_between_balls_immediately() {
+        let parameters = SupportParameters {
+            rule_ball_weight: 1.0,
+            ..Default::default()
+        };
+        let now = UNIX_EPOCH + Duration::from_secs(100);
+        let rule_ball = ball_at(point![3.0, 0.0], now);
+        let ball = ball_at(point![-2.0, 1.0], now);
+
+        let mut rule_ball_fade = RuleBallFade::default();
+        rule_ball_fade.update(Some(rule_ball), Some(ball), now, &parameters);
+        let (chosen_ball, _) = support_ball(&rule_ball_fade, Some(ball), Isometry2::identity());
+        assert_relative_eq!(chosen_ball.ball_in_field, rule_ball.ball_in_field);
+
+        rule_ball_fade.update(None, Some(ball), now, &parameters);
+        let (chosen_ball, _) = support_ball(&rule_ball_fade, Some(ball), Isometry2::identity());
+        assert_relative_eq!(chosen_ball.ball_in_field, ball.ball_in_field);
+    }
+
+    #[test]
+    fn smoothing_factor_of_zero_reaches_target_immediately() {
+        let last_pose = Isometry2::new(vector![0.0, 0.0], 0.0);
+        let target_pose = Isometry2::new(vector![1.0, 2.0], 1.0);
+
+        let smoothed_pose = smooth_pose(last_pose, target_pose, 0.0);
+
+        assert_relative_eq!(smoothed_pose, target_pose, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn smoothing_factors_of_one_and_above_still_approach_target() {
+        let last_pose = Isometry2::new(vector![0.0, 0.0], 0.0);
+        let target_pose = Isometry2::new(vector![1.0, 0.0], 1.0);
+
+        for smoothing_factor in [1.0, 1.5, 10.0] {
+            let smoothed_pose = smooth_pose(last_pose, target_pose, smoothing_factor);
+
+            assert!(smoothed_pose.translation.x > 0.0);
+            assert!(smoothed_pose.translation.x < 1.0);
+            assert!(smoothed_pose.rotation.angle() > 0.0);
+            assert!(smoothed_pose.rotation.angle() < 1.0);
+        }
+    }
 }
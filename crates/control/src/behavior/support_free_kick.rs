@@ -0,0 +1,66 @@
+use framework::AdditionalOutput;
+use geometry::look_at::LookAt;
+use nalgebra::{point, Isometry2, UnitComplex, Vector2};
+use spl_network_messages::{SubState, Team};
+use types::{
+    field_dimensions::FieldDimensions, filtered_game_controller_state::FilteredGameControllerState,
+    filtered_game_state::FilteredGameState, motion_command::MotionCommand,
+    parameters::SupportFreeKickParameters, path_obstacles::PathObstacle, world_state::WorldState,
+};
+
+use super::{head::LookAction, walk_to_pose::WalkAndStand};
+
+/// Positions supporters behind the ball during own free kicks, each on the side of the ball it
+/// already is on to spread out. Returns `None` outside of own free kicks so that the regular
+/// support action of the role takes over.
+pub fn execute(
+    world_state: &WorldState,
+    field_dimensions: &FieldDimensions,
+    parameters: &SupportFreeKickParameters,
+    walk_and_stand: &WalkAndStand,
+    look_action: &LookAction,
+    path_obstacles_output: &mut AdditionalOutput<Vec<PathObstacle>>,
+) -> Option<MotionCommand> {
+    match world_state.filtered_game_controller_state {
+        Some(FilteredGameControllerState {
+            game_state:
+                FilteredGameState::Playing {
+                    ball_is_free: false,
+                    ..
+                },
+            kicking_team: Team::Hulks,
+            sub_state: Some(sub_state),
+            ..
+        }) if !matches!(sub_state, SubState::PenaltyKick) => {}
+        _ => return None,
+    }
+    let robot_to_field = world_state.robot.robot_to_field?;
+    let ball = world_state.rule_ball.or(world_state.ball)?.ball_in_field;
+
+    let robot_position = robot_to_field * point![0.0, 0.0];
+    let spread_angle = if robot_position.y >= ball.y {
+        -parameters.spread_angle
+    } else {
+        parameters.spread_angle
+    };
+    let supporting_position =
+        ball + UnitComplex::new(spread_angle) * -(Vector2::x() * parameters.distance_to_ball);
+    let clamped_position = point![
+        supporting_position.x.clamp(
+            -field_dimensions.length / 2.0,
+            field_dimensions.length / 2.0
+        ),
+        supporting_position
+            .y
+            .clamp(-field_dimensions.width / 2.0, field_dimensions.width / 2.0)
+    ];
+    let support_pose = Isometry2::new(
+        clamped_position.coords,
+        clamped_position.look_at(&ball).angle(),
+    );
+    walk_and_stand.execute(
+        robot_to_field.inverse() * support_pose,
+        look_action.execute(),
+        path_obstacles_output,
+    )
+}
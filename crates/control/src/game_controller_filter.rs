@@ -63,12 +63,19 @@ impl GameControllerFilter {
                 kicking_team: game_controller_state_message.kicking_team,
                 last_game_state_change: self.last_game_state_change.unwrap(),
                 penalties: game_controller_state_message.hulks_team.clone().into(),
+                goal_keeper_player_number: game_controller_state_message
+                    .hulks_team
+                    .goal_keeper_player_number,
                 remaining_amount_of_messages: game_controller_state_message
                     .hulks_team
                     .remaining_amount_of_messages,
                 sub_state: game_controller_state_message.sub_state,
                 hulks_team_is_home_after_coin_toss: game_controller_state_message
                     .hulks_team_is_home_after_coin_toss,
+                half: game_controller_state_message.half,
+                remaining_time_in_half: game_controller_state_message.remaining_time_in_half,
+                hulks_score: game_controller_state_message.hulks_team.score,
+                opponent_score: game_controller_state_message.opponent_team.score,
             });
         }
         Ok(MainOutputs {
@@ -65,6 +65,7 @@ impl GameControllerStateFilter {
             game_phase: context.game_controller_state.game_phase,
             kicking_team: context.game_controller_state.kicking_team,
             penalties: context.game_controller_state.penalties,
+            goal_keeper_player_number: context.game_controller_state.goal_keeper_player_number,
             remaining_number_of_messages: context
                 .game_controller_state
                 .remaining_amount_of_messages,
@@ -72,6 +73,10 @@ impl GameControllerStateFilter {
             own_team_is_home_after_coin_toss: context
                 .game_controller_state
                 .hulks_team_is_home_after_coin_toss,
+            half: context.game_controller_state.half,
+            remaining_time_in_half: context.game_controller_state.remaining_time_in_half,
+            own_score: context.game_controller_state.hulks_score,
+            opponent_score: context.game_controller_state.opponent_score,
         };
         Ok(MainOutputs {
             filtered_game_controller_state: Some(filtered_game_controller_state).into(),
@@ -1,10 +1,10 @@
-use std::time::Duration;
+use std::{mem::discriminant, time::Duration};
 
 use color_eyre::Result;
 use context_attribute::context;
 use filtering::low_pass_filter::LowPassFilter;
 use framework::{AdditionalOutput, MainOutput};
-use log::warn;
+use log::{info, warn};
 use nalgebra::{Isometry3, Point3, Vector2, Vector3};
 use serde::{Deserialize, Serialize};
 use types::{
@@ -13,7 +13,9 @@ use types::{
     motion_command::{KickVariant, MotionCommand},
     motion_selection::{MotionSafeExits, MotionType},
     motor_commands::MotorCommands,
-    parameters::{KickStepsParameters, StepPlannerParameters, WalkingEngineParameters},
+    parameters::{
+        FootLiftTrajectory, KickStepsParameters, StepPlannerParameters, WalkingEngineParameters,
+    },
     robot_kinematics::RobotKinematics,
     sensor_data::{InertialMeasurementUnitData, SensorData},
     step_adjustment::StepAdjustment,
@@ -25,7 +27,7 @@ use types::{
 use self::{
     arms::SwingingArm,
     balancing::{step_adjustment, support_leg_gyro_balancing, swing_leg_foot_leveling},
-    engine::{calculate_foot_to_robot, parabolic_return, parabolic_step},
+    engine::{calculate_foot_to_robot, foot_lift, parabolic_step},
     foot_offsets::FootOffsets,
     kicking::apply_joint_overrides,
     walk_state::WalkState,
@@ -113,6 +115,15 @@ pub struct WalkingEngine {
 
     forward_adjustment_was_active: bool,
     backward_adjustment_was_active: bool,
+
+    /// why the last step phase ended, reported with walk state transitions
+    last_step_end: Option<StepEnd>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+enum StepEnd {
+    SupportChanged,
+    TimedOut,
 }
 
 #[context]
@@ -126,6 +137,9 @@ pub struct CycleContext {
     step_adjustment: AdditionalOutput<StepAdjustment, "step_adjustment">,
     planned_step_duration: AdditionalOutput<Duration, "walking_engine.planned_step_duration">,
     t: AdditionalOutput<Duration, "walking_engine.t">,
+    remaining_step_duration:
+        AdditionalOutput<Option<Duration>, "walking_engine.remaining_step_duration">,
+    feasible_kick_side: AdditionalOutput<Option<Side>, "walking_engine.feasible_kick_side">,
     t_on_last_phase_end: AdditionalOutput<Duration, "walking_engine.t_on_last_phase_end">,
     normalized_forward_speed: AdditionalOutput<f32, "walking_engine.normalized_forward_speed">,
     has_support_changed: AdditionalOutput<bool, "walking_engine.has_support_changed">,
@@ -190,7 +204,10 @@ impl WalkingEngine {
         );
 
         let is_step_started_this_cycle = self.t.is_zero();
-        if *context.has_ground_contact {
+        let previous_walk_state = self.walk_state;
+        if context.config.force_standing {
+            self.walk_state = WalkState::Standing;
+        } else if *context.has_ground_contact {
             if is_step_started_this_cycle {
                 self.initialize_step_states_from_request(
                     *context.walk_command,
@@ -202,6 +219,19 @@ impl WalkingEngine {
         } else {
             self.walk_state = WalkState::Standing;
         }
+        if context.config.log_walk_state_transitions
+            && discriminant(&previous_walk_state) != discriminant(&self.walk_state)
+        {
+            let trigger = match (context.config.force_standing, *context.has_ground_contact) {
+                (true, _) => "force_standing",
+                (false, false) => "no_ground_contact",
+                (false, true) => "step_started",
+            };
+            info!(
+                "walk state transition: from={previous_walk_state:?} to={:?} trigger={trigger} last_step_end={:?} walk_command={:?}",
+                self.walk_state, self.last_step_end, context.walk_command,
+            );
+        }
 
         match &self.walk_state {
             WalkState::Standing => self.reset(),
@@ -212,11 +242,17 @@ impl WalkingEngine {
                     &mut context.step_adjustment,
                 );
             }
-            WalkState::Kicking(..) => self.kick_cycle(last_cycle_duration),
+            WalkState::Kicking(..) => {
+                self.kick_cycle(last_cycle_duration, context.config.foot_lift_trajectory)
+            }
         }
 
         let left_foot_pressure = context.sensor_data.force_sensitive_resistors.left.sum();
         let right_foot_pressure = context.sensor_data.force_sensitive_resistors.right.sum();
+        let maximal_step_duration = match self.walk_state {
+            WalkState::Starting(_) => context.config.starting_maximal_step_duration,
+            _ => context.config.maximal_step_duration,
+        };
         let has_support_changed = match self.swing_side {
             Side::Left => left_foot_pressure > context.config.foot_pressure_threshold,
             Side::Right => right_foot_pressure > context.config.foot_pressure_threshold,
@@ -237,9 +273,11 @@ impl WalkingEngine {
                 self.number_of_unstable_steps = 0;
             }
             self.number_of_timeouted_steps = 0;
+            self.last_step_end = Some(StepEnd::SupportChanged);
             self.end_step_phase();
-        } else if self.t > context.config.maximal_step_duration {
+        } else if self.t > maximal_step_duration {
             self.number_of_timeouted_steps += 1;
+            self.last_step_end = Some(StepEnd::TimedOut);
             self.end_step_phase();
         }
 
@@ -322,6 +360,13 @@ impl WalkingEngine {
             );
             support_leg_adjustment = support_leg_adjustment + support_leg_gyro_balancing;
         }
+        if let WalkState::Starting(_) = self.walk_state {
+            let support_leg_gyro_balancing = support_leg_gyro_balancing(
+                self.filtered_gyro.state() * context.config.starting_step_gyro_balance_factor,
+                context.config.gyro_balance_factors,
+            );
+            support_leg_adjustment = support_leg_adjustment + support_leg_gyro_balancing;
+        }
 
         adjust_legs(
             &mut left_leg,
@@ -338,6 +383,14 @@ impl WalkingEngine {
             .planned_step_duration
             .fill_if_subscribed(|| self.planned_step_duration);
         context.t.fill_if_subscribed(|| self.t);
+        context
+            .remaining_step_duration
+            .fill_if_subscribed(|| self.remaining_step_duration());
+        context.feasible_kick_side.fill_if_subscribed(|| {
+            [Side::Left, Side::Right]
+                .into_iter()
+                .find(|kick_side| self.is_kick_feasible(*kick_side))
+        });
         context
             .t_on_last_phase_end
             .fill_if_subscribed(|| self.t_on_last_phase_end);
@@ -366,10 +419,18 @@ impl WalkingEngine {
 
         let leg_stiffness = match self.walk_state {
             WalkState::Standing => context.config.leg_stiffness_stand,
-            WalkState::Starting(_)
-            | WalkState::Walking(_)
-            | WalkState::Kicking(..)
-            | WalkState::Stopping => context.config.leg_stiffness_walk,
+            WalkState::Starting(_) => {
+                let walk_fraction = context
+                    .config
+                    .starting_leg_stiffness_walk_fraction
+                    .clamp(0.0, 1.0);
+                context.config.leg_stiffness_stand
+                    + walk_fraction
+                        * (context.config.leg_stiffness_walk - context.config.leg_stiffness_stand)
+            }
+            WalkState::Walking(_) | WalkState::Kicking(..) | WalkState::Stopping => {
+                context.config.leg_stiffness_walk
+            }
         };
         let stiffnesses = BodyJoints {
             left_arm: ArmJoints::fill(context.config.arm_stiffness),
@@ -392,6 +453,21 @@ impl WalkingEngine {
         })
     }
 
+    /// Time until the currently executed step is planned to end, `None` if not starting or walking
+    pub fn remaining_step_duration(&self) -> Option<Duration> {
+        match self.walk_state {
+            WalkState::Starting(_) | WalkState::Walking(_) => {
+                Some(self.planned_step_duration.saturating_sub(self.t))
+            }
+            WalkState::Standing | WalkState::Kicking(..) | WalkState::Stopping => None,
+        }
+    }
+
+    /// Whether a kick with `kick_side` requested now would be started with the next step
+    pub fn is_kick_feasible(&self, kick_side: Side) -> bool {
+        self.walk_state.can_start_kick(kick_side, self.swing_side)
+    }
+
     fn filter_robot_tilt_shift(
         &mut self,
         robot_kinematics: &RobotKinematics,
@@ -420,9 +496,23 @@ impl WalkingEngine {
         self.left_foot_t0 = self.left_foot;
         self.right_foot_t0 = self.right_foot;
         self.turn_t0 = self.turn;
+        let last_walk_state = self.walk_state;
         self.walk_state =
             self.walk_state
                 .next_walk_state(walk_command, self.swing_side, kick_steps);
+        if let (WalkState::Starting(_), WalkState::Walking(requested_step), Some(threshold)) = (
+            last_walk_state,
+            self.walk_state,
+            config.starting_turn_in_place_threshold,
+        ) {
+            if requested_step.turn.abs() > threshold {
+                // large turns are started in place, translation is picked up by the next request
+                self.walk_state = WalkState::Walking(Step {
+                    turn: requested_step.turn,
+                    ..Step::zero()
+                });
+            }
+        }
 
         if self.number_of_timeouted_steps >= config.max_number_of_timeouted_steps {
             self.current_step = config.emergency_step;
@@ -454,8 +544,18 @@ impl WalkingEngine {
                 self.swing_side = Side::Left;
                 self.max_swing_foot_lift = 0.0;
             }
-            WalkState::Starting(_) => {
-                self.current_step = Step::zero();
+            WalkState::Starting(requested_step) => {
+                let translation_fraction =
+                    config.starting_step_translation_fraction.clamp(0.0, 1.0);
+                self.current_step = clamp_to_anatomic_constraints(
+                    Step {
+                        forward: requested_step.forward * translation_fraction,
+                        left: requested_step.left * translation_fraction,
+                        turn: 0.0,
+                    },
+                    swing_side,
+                    inside_turn_ratio(config, swing_side),
+                );
                 self.planned_step_duration = config.starting_step_duration;
                 self.swing_side = swing_side.opposite();
                 self.max_swing_foot_lift = config.starting_step_foot_lift;
@@ -466,7 +566,7 @@ impl WalkingEngine {
                 let requested_step = clamp_to_anatomic_constraints(
                     requested_step,
                     next_support_side,
-                    config.inside_turn_ratio,
+                    inside_turn_ratio(config, next_support_side),
                 );
                 let forward_acceleration = requested_step.forward - last_step.forward;
                 self.current_step = Step {
@@ -570,11 +670,17 @@ impl WalkingEngine {
     fn next_foot_offsets(
         &mut self,
         planned_step: Step,
+        foot_lift_trajectory: FootLiftTrajectory,
     ) -> (FootOffsets, FootOffsets, f32, f32, f32) {
         match self.swing_side {
             Side::Left => {
                 let (support_foot, swing_foot, turn, support_foot_lift, swing_foot_lift) = self
-                    .calculate_foot_offsets(planned_step, self.right_foot_t0, self.left_foot_t0);
+                    .calculate_foot_offsets(
+                        planned_step,
+                        self.right_foot_t0,
+                        self.left_foot_t0,
+                        foot_lift_trajectory,
+                    );
                 (
                     swing_foot,
                     support_foot,
@@ -585,7 +691,12 @@ impl WalkingEngine {
             }
             Side::Right => {
                 let (support_foot, swing_foot, turn, support_foot_lift, swing_foot_lift) = self
-                    .calculate_foot_offsets(planned_step, self.left_foot_t0, self.right_foot_t0);
+                    .calculate_foot_offsets(
+                        planned_step,
+                        self.left_foot_t0,
+                        self.right_foot_t0,
+                        foot_lift_trajectory,
+                    );
                 (
                     support_foot,
                     swing_foot,
@@ -602,6 +713,7 @@ impl WalkingEngine {
         planned_step: Step,
         support_foot_t0: FootOffsets,
         swing_foot_t0: FootOffsets,
+        foot_lift_trajectory: FootLiftTrajectory,
     ) -> (FootOffsets, FootOffsets, f32, f32, f32) {
         let linear_time =
             (self.t.as_secs_f32() / self.planned_step_duration.as_secs_f32()).clamp(0.0, 1.0);
@@ -631,14 +743,15 @@ impl WalkingEngine {
         let step_midpoint = 0.5;
 
         let support_foot_lift = self.max_foot_lift_last_step
-            * parabolic_return(
+            * foot_lift(
                 ((self.t_on_last_phase_end.as_secs_f32() + self.t.as_secs_f32())
                     / self.last_planned_step_duration.as_secs_f32())
                 .clamp(0.0, 1.0),
                 step_midpoint,
+                foot_lift_trajectory,
             );
         let swing_foot_lift =
-            self.max_swing_foot_lift * parabolic_return(linear_time, step_midpoint);
+            self.max_swing_foot_lift * foot_lift(linear_time, step_midpoint, foot_lift_trajectory);
 
         (
             support_foot,
@@ -671,7 +784,7 @@ impl WalkingEngine {
             next_turn,
             next_left_foot_lift,
             next_right_foot_lift,
-        ) = self.next_foot_offsets(self.current_step);
+        ) = self.next_foot_offsets(self.current_step, config.foot_lift_trajectory);
         let (
             adjusted_left_foot,
             adjusted_right_foot,
@@ -712,7 +825,7 @@ impl WalkingEngine {
         self.remaining_stabilizing_steps = adjusted_remaining_steps
     }
 
-    fn kick_cycle(&mut self, cycle_duration: Duration) {
+    fn kick_cycle(&mut self, cycle_duration: Duration, foot_lift_trajectory: FootLiftTrajectory) {
         self.t += cycle_duration;
         let (
             next_left_walk_request,
@@ -720,7 +833,7 @@ impl WalkingEngine {
             next_turn,
             next_left_foot_lift,
             next_right_foot_lift,
-        ) = self.next_foot_offsets(self.current_step);
+        ) = self.next_foot_offsets(self.current_step, foot_lift_trajectory);
         self.left_foot = next_left_walk_request;
         self.right_foot = next_right_walk_request;
         self.turn = next_turn;
@@ -791,6 +904,14 @@ fn adjust_legs(
     *last_right_leg_adjustment = limited_right_leg_adjustment;
 }
 
+fn inside_turn_ratio(config: &WalkingEngineParameters, support_side: Side) -> f32 {
+    match support_side {
+        Side::Left => config.left_support_inside_turn_ratio,
+        Side::Right => config.right_support_inside_turn_ratio,
+    }
+    .unwrap_or(config.inside_turn_ratio)
+}
+
 fn clamp_to_anatomic_constraints(
     request: Step,
     support_side: Side,
@@ -823,3 +944,42 @@ fn clamp_to_anatomic_constraints(
         turn: clamped_turn,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn inside_turn_is_clamped_per_support_side() {
+        let config = WalkingEngineParameters {
+            inside_turn_ratio: 0.5,
+            left_support_inside_turn_ratio: Some(0.2),
+            right_support_inside_turn_ratio: None,
+            ..Default::default()
+        };
+        let turn_left = Step {
+            forward: 0.0,
+            left: 0.0,
+            turn: 1.0,
+        };
+        let turn_right = Step {
+            turn: -1.0,
+            ..turn_left
+        };
+
+        let clamped = clamp_to_anatomic_constraints(
+            turn_left,
+            Side::Left,
+            inside_turn_ratio(&config, Side::Left),
+        );
+        assert_relative_eq!(clamped.turn, 0.2);
+        let clamped = clamp_to_anatomic_constraints(
+            turn_right,
+            Side::Right,
+            inside_turn_ratio(&config, Side::Right),
+        );
+        assert_relative_eq!(clamped.turn, -0.5);
+    }
+}
@@ -1,5 +1,9 @@
+use std::f32::consts::FRAC_PI_2;
+
 use nalgebra::{Isometry3, Vector3};
-use types::{robot_dimensions::RobotDimensions, support_foot::Side};
+use types::{
+    parameters::FootLiftTrajectory, robot_dimensions::RobotDimensions, support_foot::Side,
+};
 
 use super::foot_offsets::FootOffsets;
 
@@ -40,6 +44,20 @@ pub fn parabolic_return(x: f32, midpoint: f32) -> f32 {
     }
 }
 
+/// Rises from 0 to 1 until `midpoint` and returns to 0 at `x == 1`
+pub fn foot_lift(x: f32, midpoint: f32, trajectory: FootLiftTrajectory) -> f32 {
+    let progress = if x < midpoint {
+        x / midpoint - 1.0
+    } else {
+        (x - midpoint) / (1.0 - midpoint)
+    };
+    match trajectory {
+        FootLiftTrajectory::Parabola => 1.0 - progress.powi(2),
+        FootLiftTrajectory::HalfSine => (FRAC_PI_2 * (1.0 - progress.abs())).sin(),
+        FootLiftTrajectory::Smoothstep => parabolic_return(x, midpoint),
+    }
+}
+
 pub fn parabolic_step(x: f32) -> f32 {
     if x < 0.5 {
         2.0 * x * x
@@ -47,3 +65,29 @@ pub fn parabolic_step(x: f32) -> f32 {
         4.0 * x - 2.0 * x * x - 1.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn foot_lift_trajectories_reach_apex_at_midpoint() {
+        for trajectory in [
+            FootLiftTrajectory::Parabola,
+            FootLiftTrajectory::HalfSine,
+            FootLiftTrajectory::Smoothstep,
+        ] {
+            for midpoint in [0.3, 0.5, 0.7] {
+                assert_relative_eq!(foot_lift(0.0, midpoint, trajectory), 0.0, epsilon = 1e-4);
+                assert_relative_eq!(
+                    foot_lift(midpoint, midpoint, trajectory),
+                    1.0,
+                    epsilon = 1e-4
+                );
+                assert_relative_eq!(foot_lift(1.0, midpoint, trajectory), 0.0, epsilon = 1e-4);
+            }
+        }
+    }
+}
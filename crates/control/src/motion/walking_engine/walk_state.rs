@@ -21,6 +21,14 @@ impl Default for WalkState {
 }
 
 impl WalkState {
+    /// Whether a kick with `kick_side` would be started at the next step instead of being deferred
+    pub fn can_start_kick(self, kick_side: Side, swing_side: Side) -> bool {
+        matches!(
+            self,
+            WalkState::Starting(_) | WalkState::Walking(_) | WalkState::Stopping
+        ) && kick_side == swing_side.opposite()
+    }
+
     pub fn next_walk_state(
         self,
         requested_walk_action: WalkCommand,
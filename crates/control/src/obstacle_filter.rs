@@ -101,7 +101,7 @@ impl ObstacleFilter {
             for network_robot_obstacle in network_robot_obstacles {
                 self.update_hypotheses_with_measurement(
                     *network_robot_obstacle,
-                    ObstacleKind::Robot,
+                    ObstacleKind::Teammate,
                     *detection_time,
                     context
                         .obstacle_filter_parameters
@@ -216,7 +216,7 @@ impl ObstacleFilter {
                         *context.goal_post_obstacle_radius,
                         *context.goal_post_obstacle_radius,
                     ),
-                    ObstacleKind::Robot => (
+                    ObstacleKind::Robot | ObstacleKind::Teammate => (
                         *context.robot_obstacle_radius_at_hip_height,
                         *context.robot_obstacle_radius_at_foot_height,
                     ),
@@ -299,11 +299,8 @@ impl ObstacleFilter {
                 detected_position.coords,
                 measurement_noise * detected_position.coords.norm_squared(),
             );
-            hypothesis.obstacle_kind = match hypothesis.obstacle_kind {
-                ObstacleKind::Robot => hypothesis.obstacle_kind,
-                ObstacleKind::Unknown => detected_obstacle_kind,
-                _ => panic!("Unexpected obstacle kind"),
-            };
+            hypothesis.obstacle_kind =
+                merge_obstacle_kinds(hypothesis.obstacle_kind, detected_obstacle_kind);
             hypothesis.measurement_count += 1;
             hypothesis.last_update = detection_time;
         });
@@ -356,11 +353,10 @@ impl ObstacleFilter {
                         hypothesis.state.mean,
                         hypothesis.state.covariance,
                     );
-                    existing_hypothesis.obstacle_kind = match existing_hypothesis.obstacle_kind {
-                        ObstacleKind::Robot => existing_hypothesis.obstacle_kind,
-                        ObstacleKind::Unknown => hypothesis.obstacle_kind,
-                        _ => panic!("Unexpected obstacle kind"),
-                    };
+                    existing_hypothesis.obstacle_kind = merge_obstacle_kinds(
+                        existing_hypothesis.obstacle_kind,
+                        hypothesis.obstacle_kind,
+                    );
                 }
                 None => deduplicated_hypotheses.push(hypothesis),
             }
@@ -369,6 +365,20 @@ impl ObstacleFilter {
     }
 }
 
+fn merge_obstacle_kinds(existing: ObstacleKind, measured: ObstacleKind) -> ObstacleKind {
+    match (existing, measured) {
+        // only network messages tell teammates apart from opponents, a hypothesis stays a
+        // teammate once it has been matched with the position sent by one
+        (ObstacleKind::Teammate, _)
+        | (ObstacleKind::Robot | ObstacleKind::Unknown, ObstacleKind::Teammate) => {
+            ObstacleKind::Teammate
+        }
+        (ObstacleKind::Robot, _) => ObstacleKind::Robot,
+        (ObstacleKind::Unknown, measured) => measured,
+        _ => panic!("Unexpected obstacle kind"),
+    }
+}
+
 fn calculate_goal_post_positions(
     current_robot_to_field: Option<&Isometry2<f32>>,
     field_dimensions: &FieldDimensions,
@@ -392,3 +402,28 @@ fn calculate_goal_post_positions(
         .flatten()
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teammates_are_never_downgraded_to_robots() {
+        assert!(matches!(
+            merge_obstacle_kinds(ObstacleKind::Teammate, ObstacleKind::Robot),
+            ObstacleKind::Teammate
+        ));
+        assert!(matches!(
+            merge_obstacle_kinds(ObstacleKind::Robot, ObstacleKind::Teammate),
+            ObstacleKind::Teammate
+        ));
+        assert!(matches!(
+            merge_obstacle_kinds(ObstacleKind::Unknown, ObstacleKind::Robot),
+            ObstacleKind::Robot
+        ));
+        assert!(matches!(
+            merge_obstacle_kinds(ObstacleKind::Robot, ObstacleKind::Unknown),
+            ObstacleKind::Robot
+        ));
+    }
+}
@@ -36,15 +36,68 @@ impl<'context, DataType> AdditionalOutput<'context, DataType> {
 }
 
 pub fn should_be_filled(subscribed_output: &str, additional_output_path: &str) -> bool {
-    subscribed_output == additional_output_path
-        || subscribed_output.starts_with(&format!("{additional_output_path}."))
-        || additional_output_path.starts_with(&format!("{subscribed_output}."))
+    // Evaluated for every additional output in every cycle, therefore must not allocate
+    is_path_or_descendant(subscribed_output, additional_output_path)
+        || is_path_or_descendant(additional_output_path, subscribed_output)
+}
+
+fn is_path_or_descendant(path: &str, ancestor: &str) -> bool {
+    match path.strip_prefix(ancestor) {
+        Some(remainder) => remainder.is_empty() || remainder.starts_with('.'),
+        None => false,
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
     use super::*;
 
+    struct CountingAllocator;
+
+    thread_local! {
+        static NUMBER_OF_ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            NUMBER_OF_ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+            System.dealloc(pointer, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn count_allocations(callback: impl FnOnce()) -> usize {
+        let before = NUMBER_OF_ALLOCATIONS.with(Cell::get);
+        callback();
+        NUMBER_OF_ALLOCATIONS.with(Cell::get) - before
+    }
+
+    #[test]
+    fn unsubscribed_additional_outputs_do_not_allocate() {
+        let subscribed_outputs = ["additional_outputs.a", "additional_outputs.b.c"];
+        let mut data: Option<Vec<f32>> = None;
+        let number_of_allocations = count_allocations(|| {
+            let is_subscribed = subscribed_outputs.iter().any(|subscribed_output| {
+                should_be_filled(subscribed_output, "additional_outputs.b.d")
+            });
+            let mut output = AdditionalOutput::new(is_subscribed, &mut data);
+            output.fill_if_subscribed(|| vec![0.0; 1024]);
+        });
+        assert_eq!(number_of_allocations, 0);
+        assert!(data.is_none());
+    }
+
     #[test]
     fn should_be_filled_is_correct_for_type_hierarchy() {
         let cases = [
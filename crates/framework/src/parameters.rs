@@ -6,6 +6,7 @@ use serde::Deserialize;
 pub struct Parameters {
     pub communication_addresses: Option<String>,
     pub cycler_instances_to_be_recorded: HashSet<String>,
+    pub enable_instrumentation: bool,
     pub hardware_parameters: PathBuf,
     pub parameters_directory: PathBuf,
 }
@@ -0,0 +1,78 @@
+const LENGTH_PREFIX_SIZE: usize = std::mem::size_of::<u64>();
+
+/// Appends the data written by `write` to the buffer, prefixed by its length as little-endian
+/// `u64`, so that readers can skip it without knowing (or being able to deserialize) its type
+pub fn write_length_prefixed<Error>(
+    buffer: &mut Vec<u8>,
+    write: impl FnOnce(&mut Vec<u8>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let length_position = buffer.len();
+    buffer.extend_from_slice(&[0; LENGTH_PREFIX_SIZE]);
+    write(buffer)?;
+    let length = (buffer.len() - length_position - LENGTH_PREFIX_SIZE) as u64;
+    buffer[length_position..length_position + LENGTH_PREFIX_SIZE]
+        .copy_from_slice(&length.to_le_bytes());
+    Ok(())
+}
+
+/// Takes data written by `write_length_prefixed` from the front of `data`, returns `None` if
+/// `data` is truncated
+pub fn read_length_prefixed<'data>(data: &mut &'data [u8]) -> Option<&'data [u8]> {
+    let length_prefix = data.get(..LENGTH_PREFIX_SIZE)?;
+    let length = u64::from_le_bytes(length_prefix.try_into().ok()?);
+    let end = LENGTH_PREFIX_SIZE.checked_add(length.try_into().ok()?)?;
+    let prefixed_data = data.get(LENGTH_PREFIX_SIZE..end)?;
+    *data = &data[end..];
+    Some(prefixed_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::deserialize_not_implemented;
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize)]
+    struct NodeWithSkippedField {
+        value: u32,
+        #[serde(skip, default = "deserialize_not_implemented")]
+        _skipped: Vec<u8>,
+    }
+
+    #[test]
+    fn undeserializable_data_is_skipped() {
+        let node = NodeWithSkippedField {
+            value: 42,
+            _skipped: vec![1, 2, 3],
+        };
+        let mut buffer = Vec::new();
+        write_length_prefixed(&mut buffer, |buffer| bincode::serialize_into(buffer, &node))
+            .unwrap();
+        bincode::serialize_into(&mut buffer, &1337_u32).unwrap();
+
+        let mut data = buffer.as_slice();
+        let node_data = read_length_prefixed(&mut data).unwrap();
+        let following_value: u32 = bincode::deserialize_from(&mut data).unwrap();
+
+        assert_eq!(node_data, bincode::serialize(&node).unwrap());
+        assert_eq!(following_value, 1337);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn truncated_data_is_rejected() {
+        let mut buffer = Vec::new();
+        write_length_prefixed(&mut buffer, |buffer| {
+            buffer.extend_from_slice(&[1, 2, 3]);
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+
+        let mut data = &buffer[..buffer.len() - 1];
+        assert_eq!(read_length_prefixed(&mut data), None);
+        let mut data = &buffer[..4];
+        assert_eq!(read_length_prefixed(&mut data), None);
+    }
+}
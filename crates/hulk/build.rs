@@ -28,6 +28,8 @@ fn main() -> Result<()> {
                     "vision::robot_detection",
                     "vision::segment_filter",
                 ],
+                enable_cycle_budget: false,
+                recording_encoder: "crate::cyclers::BincodeEncoder",
             },
             CyclerManifest {
                 name: "Control",
@@ -86,6 +88,8 @@ fn main() -> Result<()> {
                     "control::whistle_filter",
                     "control::world_state_composer",
                 ],
+                enable_cycle_budget: true,
+                recording_encoder: "crate::cyclers::BincodeEncoder",
             },
             CyclerManifest {
                 name: "SplNetwork",
@@ -93,6 +97,8 @@ fn main() -> Result<()> {
                 instances: vec![""],
                 setup_nodes: vec!["spl_network::message_receiver"],
                 nodes: vec![],
+                enable_cycle_budget: false,
+                recording_encoder: "crate::cyclers::BincodeEncoder",
             },
             CyclerManifest {
                 name: "Audio",
@@ -100,6 +106,8 @@ fn main() -> Result<()> {
                 instances: vec![""],
                 setup_nodes: vec!["audio::microphone_recorder"],
                 nodes: vec!["audio::whistle_detection"],
+                enable_cycle_budget: false,
+                recording_encoder: "crate::cyclers::BincodeEncoder",
             },
         ],
     };
@@ -75,5 +75,6 @@ fn main() -> Result<()> {
         ids.head_id,
         keep_running,
         framework_parameters.cycler_instances_to_be_recorded,
+        framework_parameters.enable_instrumentation,
     )
 }
@@ -97,6 +97,8 @@ pub struct Cycler {
     pub instances: Vec<InstanceName>,
     pub setup_nodes: Vec<Node>,
     pub cycle_nodes: Vec<Node>,
+    pub enable_cycle_budget: bool,
+    pub recording_encoder: syn::Path,
 }
 
 impl Cycler {
@@ -116,6 +118,12 @@ impl Cycler {
             .iter()
             .map(|specification| Node::try_from_node_name(specification, root))
             .collect::<Result<Vec<_>, _>>()?;
+        let recording_encoder =
+            syn::parse_str(cycler_manifest.recording_encoder).map_err(|_| {
+                Error::InvalidRecordingEncoder {
+                    path: cycler_manifest.recording_encoder.to_string(),
+                }
+            })?;
 
         Ok(Cycler {
             name: cycler_manifest.name.to_string(),
@@ -123,6 +131,8 @@ impl Cycler {
             instances,
             setup_nodes,
             cycle_nodes,
+            enable_cycle_budget: cycler_manifest.enable_cycle_budget,
+            recording_encoder,
         })
     }
 
@@ -19,6 +19,8 @@ pub enum Error {
     },
     #[error("invalid module path")]
     InvalidModulePath,
+    #[error("invalid recording encoder path `{path}`")]
+    InvalidRecordingEncoder { path: String },
     #[error("`{node}` requires output `{output}`, but it is never produced")]
     MissingOutput { node: String, output: String },
     #[error("failed to sort nodes, circular dependency detected")]
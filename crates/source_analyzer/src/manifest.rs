@@ -14,4 +14,10 @@ pub struct CyclerManifest {
     pub instances: Vec<&'static str>,
     pub setup_nodes: Vec<&'static str>,
     pub nodes: Vec<&'static str>,
+    /// Whether cycles exceeding the `cycle_budget.$cycler_instance` parameter are counted and logged,
+    /// the measured duration starts after the setup nodes (e.g. waiting for sensor data)
+    pub enable_cycle_budget: bool,
+    /// Path of the `FrameEncoder` serializing the recording frames of this cycler, e.g.
+    /// `crate::cyclers::BincodeEncoder`
+    pub recording_encoder: &'static str,
 }
@@ -8,7 +8,7 @@ use quote::ToTokens;
 use syn::{parse_file, ImplItem, Item, ItemImpl, Type};
 
 use crate::{
-    contexts::Contexts,
+    contexts::{Contexts, Field},
     error::{Error, ParseError},
 };
 
@@ -20,6 +20,9 @@ pub struct Node {
     pub module: syn::Path,
     pub file_path: PathBuf,
     pub contexts: Contexts,
+    /// The node declares `should_cycle(&CycleContext) -> bool`, its cycle is skipped and its main
+    /// outputs are defaulted whenever this guard returns `false`
+    pub has_cycle_guard: bool,
 }
 
 pub fn parse_rust_file(file_path: impl AsRef<Path>) -> Result<syn::File, Error> {
@@ -43,28 +46,38 @@ impl Node {
             path: file_path.clone(),
         };
         let rust_file = parse_rust_file(&file_path)?;
-        let name = rust_file
+        let (name, has_cycle_guard) = rust_file
             .items
             .iter()
             .find_map(|item| match item {
                 Item::Impl(implementation) if has_new_and_cycle_method(implementation) => {
                     match *implementation.self_ty {
-                        Type::Path(ref path) => path.path.get_ident(),
+                        Type::Path(ref path) => path
+                            .path
+                            .get_ident()
+                            .map(|name| (name, has_method(implementation, "should_cycle"))),
                         _ => None,
                     }
                 }
                 _ => None,
             })
-            .ok_or_else(|| wrap_error(ParseError::new_spanned(&rust_file, "cannot find node declaration, expected a type with new(...) and cycle(...) method")))?
-            .to_string();
+            .ok_or_else(|| wrap_error(ParseError::new_spanned(&rust_file, "cannot find node declaration, expected a type with new(...) and cycle(...) method")))?;
         let contexts = Contexts::try_from_file(&rust_file).map_err(wrap_error)?;
         Ok(Self {
-            name,
+            name: name.to_string(),
             module,
             file_path,
             contexts,
+            has_cycle_guard,
         })
     }
+
+    pub fn has_required_inputs(&self) -> bool {
+        self.contexts
+            .cycle_context
+            .iter()
+            .any(|field| matches!(field, Field::RequiredInput { .. }))
+    }
 }
 
 fn file_path_from_module_path(root: &Path, module: syn::Path) -> Result<PathBuf, Error> {
@@ -81,12 +94,12 @@ fn file_path_from_module_path(root: &Path, module: syn::Path) -> Result<PathBuf,
 }
 
 fn has_new_and_cycle_method(implementation: &ItemImpl) -> bool {
+    has_method(implementation, "new") && has_method(implementation, "cycle")
+}
+
+fn has_method(implementation: &ItemImpl, name: &str) -> bool {
     implementation
         .items
         .iter()
-        .any(|item| matches!(item, ImplItem::Method(method) if method.sig.ident == "new"))
-        && implementation
-            .items
-            .iter()
-            .any(|item| matches!(item, ImplItem::Method(method) if method.sig.ident == "cycle"))
+        .any(|item| matches!(item, ImplItem::Method(method) if method.sig.ident == name))
 }
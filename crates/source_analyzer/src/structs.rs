@@ -1,15 +1,16 @@
 use std::{collections::BTreeMap, iter::once};
 
+use convert_case::{Case, Casing};
 use quote::format_ident;
 use syn::{
-    punctuated::Punctuated, AngleBracketedGenericArguments, GenericArgument, PathArguments, Type,
-    TypePath,
+    parse_quote, punctuated::Punctuated, AngleBracketedGenericArguments, GenericArgument,
+    PathArguments, Type, TypePath,
 };
 use thiserror::Error;
 
 use crate::{
     contexts::Field,
-    cyclers::{CyclerName, Cyclers},
+    cyclers::{Cycler, CyclerName, Cyclers},
     path::Path,
     struct_hierarchy::{HierarchyError, InsertionRule, StructHierarchy},
 };
@@ -104,11 +105,95 @@ impl Structs {
                     }
                 }
             }
+            add_cycler_additional_outputs(cycler, cycler_structs)?;
+            if cycler.enable_cycle_budget {
+                add_cycle_budget_parameters(cycler, &mut structs.parameters)?;
+            }
         }
         Ok(structs)
     }
 }
 
+pub const RECORDING_DECISION_PATH: &str = "recording_decision";
+pub const RECORDING_TRIGGER_PATH: &str = "recording_trigger";
+pub const RECORDING_SERIALIZATION_TIME_PATH: &str = "recording_serialization_time";
+pub const CYCLE_BUDGET_OVERRUNS_PATH: &str = "cycle_budget_overruns";
+pub const CYCLE_BUDGET_PATH: &str = "cycle_budget";
+pub const MISSING_REQUIRED_INPUTS_PATH: &str = "missing_required_inputs";
+
+fn add_cycler_additional_outputs(
+    cycler: &Cycler,
+    cycler_structs: &mut CyclerStructs,
+) -> Result<(), Error> {
+    // filled by the generated cycler itself, not by a node
+    let recording_decision_data_type: Type = parse_quote! { Option<bool> };
+    let recording_serialization_time_data_type: Type = parse_quote! { Option<std::time::Duration> };
+    let cycle_budget_overruns_data_type: Type = parse_quote! { Option<usize> };
+    let missing_required_inputs_data_type: Type = parse_quote! { Option<usize> };
+    let missing_required_inputs = cycler
+        .iter_nodes()
+        .filter(|node| node.has_required_inputs())
+        .map(|node| {
+            (
+                format!(
+                    "{MISSING_REQUIRED_INPUTS_PATH}.{}",
+                    node.name.to_case(Case::Snake)
+                ),
+                &missing_required_inputs_data_type,
+            )
+        });
+    let additional_outputs = [
+        (
+            format!("{RECORDING_DECISION_PATH}.enabled_by_configuration"),
+            &recording_decision_data_type,
+        ),
+        (
+            format!("{RECORDING_DECISION_PATH}.enabled_by_hardware"),
+            &recording_decision_data_type,
+        ),
+        (
+            format!("{RECORDING_DECISION_PATH}.enabled_by_trigger"),
+            &recording_decision_data_type,
+        ),
+        (
+            RECORDING_TRIGGER_PATH.to_string(),
+            &recording_decision_data_type,
+        ),
+        (
+            RECORDING_SERIALIZATION_TIME_PATH.to_string(),
+            &recording_serialization_time_data_type,
+        ),
+    ]
+    .into_iter()
+    .chain(cycler.enable_cycle_budget.then(|| {
+        (
+            CYCLE_BUDGET_OVERRUNS_PATH.to_string(),
+            &cycle_budget_overruns_data_type,
+        )
+    }))
+    .chain(missing_required_inputs);
+    for (path, data_type) in additional_outputs {
+        let path = Path::try_new(&path, false).expect("cycler output path has to be valid");
+        let insertion_rules = path_to_insertion_rules(&path, data_type);
+        cycler_structs.additional_outputs.insert(insertion_rules)?;
+    }
+    Ok(())
+}
+
+fn add_cycle_budget_parameters(
+    cycler: &Cycler,
+    parameters: &mut StructHierarchy,
+) -> Result<(), Error> {
+    let data_type: Type = parse_quote! { std::time::Duration };
+    let path = Path::try_new(&format!("{CYCLE_BUDGET_PATH}.$cycler_instance"), false)
+        .expect("cycle budget path has to be valid");
+    for path in path.expand_variables(&cycler.instances) {
+        let insertion_rules = path_to_insertion_rules(&path, &data_type);
+        parameters.insert(insertion_rules)?;
+    }
+    Ok(())
+}
+
 fn add_main_outputs(field: &Field, cycler_structs: &mut CyclerStructs) {
     match field {
         Field::MainOutput { data_type, name } => match &mut cycler_structs.main_outputs {
@@ -164,7 +249,7 @@ fn path_to_insertion_rules<'path>(
         }))
 }
 
-fn unwrap_option_type(data_type: Type) -> Type {
+pub fn unwrap_option_type(data_type: Type) -> Type {
     match data_type {
         Type::Path(TypePath {
             path: syn::Path { segments, .. },
@@ -324,8 +324,11 @@ pub enum SubState {
     PenaltyKick,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy)]
+#[derive(
+    Default, Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SerializeHierarchy,
+)]
 pub enum Half {
+    #[default]
     First,
     Second,
 }
@@ -9,9 +9,12 @@ pub enum Action {
     Initial,
     FallSafely,
     StandUp,
+    PenaltyShootoutHold,
     Stand,
     LookAround,
     InterceptBall,
+    HoldPossession,
+    ShieldBall,
     Calibrate,
     Dribble,
     DefendGoal,
@@ -19,11 +22,13 @@ pub enum Action {
     DefendLeft,
     DefendRight,
     DefendPenaltyKick,
+    MarkOpponent { obstacle_index: u8 },
     Jump,
     PrepareJump,
     SupportLeft,
     SupportRight,
     SupportStriker,
+    SupportFreeKick,
     Search,
     SearchForLostBall,
     WalkToKickOff,
@@ -1,6 +1,8 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
-use spl_network_messages::{GamePhase, Penalty, SubState, Team};
+use spl_network_messages::{GamePhase, Half, Penalty, PlayerNumber, SubState, Team};
 
 use crate::{filtered_game_state::FilteredGameState, players::Players};
 
@@ -11,7 +13,12 @@ pub struct FilteredGameControllerState {
     pub game_phase: GamePhase,
     pub kicking_team: Team,
     pub penalties: Players<Option<Penalty>>,
+    pub goal_keeper_player_number: PlayerNumber,
     pub remaining_number_of_messages: u16,
     pub sub_state: Option<SubState>,
     pub own_team_is_home_after_coin_toss: bool,
+    pub half: Half,
+    pub remaining_time_in_half: Duration,
+    pub own_score: u8,
+    pub opponent_score: u8,
 }
@@ -1,8 +1,8 @@
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
-use spl_network_messages::{GamePhase, GameState, Penalty, SubState, Team};
+use spl_network_messages::{GamePhase, GameState, Half, Penalty, PlayerNumber, SubState, Team};
 
 use crate::players::Players;
 
@@ -13,7 +13,12 @@ pub struct GameControllerState {
     pub kicking_team: Team,
     pub last_game_state_change: SystemTime,
     pub penalties: Players<Option<Penalty>>,
+    pub goal_keeper_player_number: PlayerNumber,
     pub remaining_amount_of_messages: u16,
     pub sub_state: Option<SubState>,
     pub hulks_team_is_home_after_coin_toss: bool,
+    pub half: Half,
+    pub remaining_time_in_half: Duration,
+    pub hulks_score: u8,
+    pub opponent_score: u8,
 }
@@ -68,6 +68,7 @@ pub mod sonar_obstacle;
 pub mod sonar_values;
 pub mod step_adjustment;
 pub mod step_plan;
+pub mod support_decision;
 pub mod support_foot;
 pub mod walk_command;
 pub mod whistle;
@@ -7,6 +7,8 @@ pub enum ObstacleKind {
     Ball,
     GoalPost,
     Robot,
+    /// Robot that was also measured via the network messages of our team
+    Teammate,
     #[default]
     Unknown,
 }
@@ -11,6 +11,7 @@ use crate::{
     motion_command::{KickVariant, MotionCommand},
     roles::Role,
     step_plan::Step,
+    support_foot::Side,
 };
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
@@ -43,6 +44,11 @@ pub struct BehaviorParameters {
     pub look_action: LookActionParameters,
     pub intercept_ball: InterceptBallParameters,
     pub initial_lookaround_duration: Duration,
+    pub support: SupportParameters,
+    pub hold_possession: HoldPossessionParameters,
+    pub shield_ball: ShieldBallParameters,
+    pub support_free_kick: SupportFreeKickParameters,
+    pub mark_opponent: MarkOpponentParameters,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
@@ -72,6 +78,60 @@ pub struct RolePositionsParameters {
     pub striker_set_position: Vector2<f32>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct SupportParameters {
+    pub forced_side: Option<Side>,
+    pub fallback: SupportFallbackParameters,
+    pub penalty_area_avoidance: f32,
+    pub target_smoothing_factor: f32,
+    pub rule_ball_weight: f32,
+    pub rule_ball_fade_duration: Duration,
+    pub stale_ball_age: Duration,
+    pub side_relative_to_ball: SupportSideParameters,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub enum SupportFallbackParameters {
+    #[default]
+    Disabled,
+    StandAndLookAround,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct HoldPossessionParameters {
+    pub enabled: bool,
+    pub minimum_goal_lead: u8,
+    pub remaining_time_threshold: Duration,
+    pub holding_distance: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub enum SupportSideParameters {
+    #[default]
+    OppositeOfBall,
+    SameAsBall,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct ShieldBallParameters {
+    pub enabled: bool,
+    pub pressure_distance: f32,
+    pub shielding_distance: f32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct SupportFreeKickParameters {
+    pub distance_to_ball: f32,
+    pub spread_angle: f32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct MarkOpponentParameters {
+    pub enabled: bool,
+    pub maximum_distance_to_own_goal: f32,
+    pub marking_distance: f32,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct SearchParameters {
     pub position_reached_distance: f32,
@@ -178,6 +238,8 @@ pub struct WalkingEngineParameters {
     pub emergency_foot_lift: f32,
     pub emergency_step: Step,
     pub emergency_step_duration: Duration,
+    pub foot_lift_trajectory: FootLiftTrajectory,
+    pub force_standing: bool,
     pub foot_pressure_threshold: f32,
     pub forward_foot_support_offset: f32,
     pub gyro_balance_factors: LegJoints<f32>,
@@ -186,6 +248,8 @@ pub struct WalkingEngineParameters {
     pub inside_turn_ratio: f32,
     pub leg_stiffness_stand: f32,
     pub leg_stiffness_walk: f32,
+    pub left_support_inside_turn_ratio: Option<f32>,
+    pub log_walk_state_transitions: bool,
     pub max_forward_acceleration: f32,
     pub max_leg_adjustment_velocity: LegJoints<f32>,
     pub max_number_of_timeouted_steps: usize,
@@ -196,12 +260,18 @@ pub struct WalkingEngineParameters {
     pub left_step_midpoint: f32,
     pub minimal_step_duration: Duration,
     pub number_of_stabilizing_steps: usize,
+    pub right_support_inside_turn_ratio: Option<f32>,
     pub stabilization_foot_lift_multiplier: f32,
     pub stabilization_foot_lift_offset: f32,
     pub stabilization_hysteresis: f32,
     pub stable_step_deviation: Duration,
+    pub starting_leg_stiffness_walk_fraction: f32,
+    pub starting_maximal_step_duration: Duration,
     pub starting_step_duration: Duration,
     pub starting_step_foot_lift: f32,
+    pub starting_step_gyro_balance_factor: f32,
+    pub starting_step_translation_fraction: f32,
+    pub starting_turn_in_place_threshold: Option<f32>,
     pub step_duration_increase: Step,
     pub step_foot_lift_increase: Step,
     pub swing_foot_imu_leveling_factor: f32,
@@ -215,6 +285,14 @@ pub struct WalkingEngineParameters {
     pub walk_hip_height: f32,
 }
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub enum FootLiftTrajectory {
+    Parabola,
+    HalfSine,
+    #[default]
+    Smoothstep,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 pub struct SwingingArmsParameters {
     pub debug_pull_back: bool,
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use serialize_hierarchy::SerializeHierarchy;
+
+use crate::support_foot::Side;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub struct SupportDecision {
+    pub side: Side,
+    pub ball_source: SupportBallSource,
+    pub offset_angle: f32,
+    pub minimum_x: f32,
+    pub maximum_x: f32,
+    pub minimum_y: f32,
+    pub maximum_y: f32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub enum SupportBallSource {
+    RuleBall,
+    Ball,
+    Blended,
+    FieldCenter,
+}
@@ -27,6 +27,8 @@ fn main() -> Result<()> {
                     "control::time_to_reach_kick_position",
                     "control::world_state_composer",
                 ],
+                enable_cycle_budget: false,
+                recording_encoder: "crate::cyclers::BincodeEncoder",
             },
             CyclerManifest {
                 name: "SplNetwork",
@@ -34,6 +36,8 @@ fn main() -> Result<()> {
                 instances: vec![""],
                 setup_nodes: vec!["spl_network::message_receiver"],
                 nodes: vec![],
+                enable_cycle_budget: false,
+                recording_encoder: "crate::cyclers::BincodeEncoder",
             },
         ],
     };
@@ -277,6 +277,10 @@ impl BehaviorCycler {
                         &mut own_database.additional_outputs.dribble_path_obstacles,
                     ),
                     AdditionalOutput::new(true, &mut own_database.additional_outputs.active_action),
+                    AdditionalOutput::new(
+                        true,
+                        &mut own_database.additional_outputs.support_decision,
+                    ),
                     &true,
                     &own_database.main_outputs.world_state,
                     &own_database.main_outputs.cycle_time,
@@ -9,7 +9,7 @@ use geometry::line_segment::LineSegment;
 use nalgebra::{vector, Isometry2, Point2, UnitComplex, Vector2};
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
-use spl_network_messages::{GamePhase, GameState, HulkMessage, PlayerNumber, Team};
+use spl_network_messages::{GamePhase, GameState, Half, HulkMessage, PlayerNumber, Team};
 use types::motion_command::{HeadMotion, OrientationMode};
 use types::{
     ball_position::BallPosition,
@@ -324,9 +324,14 @@ impl Default for State {
                 six: None,
                 seven: None,
             },
+            goal_keeper_player_number: PlayerNumber::One,
             remaining_amount_of_messages: 1200,
             sub_state: None,
             hulks_team_is_home_after_coin_toss: false,
+            half: Half::First,
+            remaining_time_in_half: Duration::from_secs(600),
+            hulks_score: 0,
+            opponent_score: 0,
         };
 
         Self {